@@ -3,7 +3,10 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// A generational ID for some peice of data. Conceptually, you can think of it as a pointer
 /// that can only be created pointing to valid data (no nulls), and automatically protects against use-after-free.
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+///
+/// Ids are ordered by index, then by generation. That ordering has no meaning in terms of the graph's structure;
+/// it only exists so that Ids can be sorted and used as keys in ordered collections like `BTreeMap`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Id {
     index: usize,
     generation: usize,
@@ -15,7 +18,43 @@ impl Debug for Id {
     }
 }
 
+/// The generation an [Id](struct.Id.html) was created in, as returned by [`Id::generation_id`](struct.Id.html#method.generation_id)
+/// and [`PGraph::current_generation`](struct.PGraph.html#method.current_generation).
+///
+/// Generations can be nondeterministic, so a GenerationId is only meaningful when compared for equality with another one.
+/// It can't be constructed or converted into a number outside of this crate.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GenerationId(usize);
+
+impl Debug for GenerationId {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "gen{}", self.0)
+    }
+}
+
 impl Id {
+    /// Gets the generation of this ID. Only useful to check whether two Ids (or an Id and a PGraph) are from the same generation.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// assert_eq!(id1.generation_id(), id2.generation_id());
+    /// assert_eq!(id1.generation_id(), g.current_generation());
+    ///
+    /// g.remove_mut(id2);
+    /// let id3 = g.add_mut(3);
+    /// assert_ne!(id1.generation_id(), id3.generation_id());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn generation_id(&self) -> GenerationId {
+        GenerationId(self.generation)
+    }
+
     /// Gets the generation of this ID. Should only be needed for debug/testing.
     /// Normal use cases should only care if two Ids generations are equal, not
     /// what that generation is, since the generation can be nondeterministic.
@@ -33,14 +72,63 @@ impl Id {
     }
 }
 
+/// The source of every generation, shared by every IdGen in the process.
+///
+/// Each IdGen created, cloned, or moved to a new generation takes the next value from the counter, so it only overflows after
+/// `usize::MAX` of those. That can't realistically happen on a 64-bit target, but a long-running process that churns through
+/// PGraphs on a 32-bit target could get there. Debug builds panic if the counter wraps around. Release builds start again from
+/// zero, after which generations can repeat, so an Id from a long-gone generation could be mistaken for a current one.
 static GENERATION: AtomicUsize = AtomicUsize::new(0);
 
+/// Takes the next generation from the global counter.
+fn fresh_generation() -> usize {
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed);
+    debug_assert_ne!(
+        generation,
+        usize::MAX,
+        "The global generation counter overflowed."
+    );
+    generation
+}
+
+/// Resets the global counter every PGraph's generations are drawn from, so the next generation created is the same as
+/// in a freshly started process. This is meant for tests that need deterministic generations (e.g. to compare `Debug` output).
+///
+/// Generations created after a reset can be the same as ones created before it, which defeats the protection Ids give against
+/// use-after-free: an Id from a PGraph that existed before the reset can look like it belongs to one created after it.
+/// Only call this when no PGraphs or Ids from before the reset will be used again, and no other thread is creating PGraphs.
+/// (Note that `cargo test` runs tests on several threads at once by default.)
+///
+/// Only available in this crate's own tests, or with the `test-util` feature.
+/// # Examples
+///
+/// ```
+/// # use pgraph::{reset_generation_counter_for_tests, PGraph};
+/// # fn main() {
+/// reset_generation_counter_for_tests();
+/// let first = PGraph::<usize, usize>::new().current_generation();
+///
+/// reset_generation_counter_for_tests();
+/// let second = PGraph::<usize, usize>::new().current_generation();
+///
+/// assert_eq!(first, second);
+/// # }
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+pub fn reset_generation_counter_for_tests() {
+    GENERATION.store(0, Ordering::Relaxed);
+}
+
 /// Keeps track of a current generation and creates new [Id](struct.Id.html)s from that generation.
-/// The generation of two IdGen instances will never be the same (that guarantee is thread-safe).
+/// The generation of two IdGen instances will never be the same (that guarantee is thread-safe), unless the global
+/// counter overflows or is reset by `reset_generation_counter_for_tests`.
 /// However, this means that the generation has no guaranteed starting point or step, so you shouldn't depend
 /// on _specific_ generations, only check for equality.
+///
+/// A fixed IdGen (see `new_fixed`) never changes its generation, and its clones share it, so its Ids only differ by index.
 pub(crate) struct IdGen {
     current_gen: usize,
+    fixed: bool,
 }
 
 impl Debug for IdGen {
@@ -54,10 +142,36 @@ impl IdGen {
     #[must_use]
     pub(crate) fn new() -> Self {
         IdGen {
-            current_gen: GENERATION.fetch_add(1, Ordering::Relaxed),
+            current_gen: fresh_generation(),
+            fixed: false,
+        }
+    }
+
+    /// Creates a new, fixed IdGen: `next_gen` does nothing, and clones keep the same generation. The generation still won't
+    /// conflict with any other IdGen's.
+    #[must_use]
+    pub(crate) fn new_fixed() -> Self {
+        IdGen {
+            fixed: true,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a new IdGen with a new generation, which is fixed iff this one is.
+    #[must_use]
+    pub(crate) fn fresh(&self) -> Self {
+        IdGen {
+            fixed: self.fixed,
+            ..Self::new()
         }
     }
 
+    /// Returns `true` iff this IdGen was created by `new_fixed` (or is a clone of one that was).
+    #[must_use]
+    pub(crate) fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
     /// Creates a new id with the given index for the current generation
     #[must_use]
     pub(crate) fn create_id(&self, index: usize) -> Id {
@@ -67,10 +181,19 @@ impl IdGen {
         }
     }
 
-    /// Increments the current generation by some number. The current generation after calling this function is guaranteed
-    /// to be strictly greater than it was before, but _how much greater_ is undefined.
+    /// Moves this IdGen to a new generation, taken from the global counter. The new generation is different from every one handed
+    /// out before it, unless the counter has wrapped around or been reset by `reset_generation_counter_for_tests`. Nothing else
+    /// about it is guaranteed (e.g. it isn't always greater). Does nothing if this IdGen is fixed.
     pub(crate) fn next_gen(&mut self) {
-        self.current_gen = GENERATION.fetch_add(1, Ordering::Relaxed);
+        if !self.fixed {
+            self.current_gen = fresh_generation();
+        }
+    }
+
+    /// Gets the IdGen's current generation as an opaque GenerationId
+    #[must_use]
+    pub(crate) fn generation_id(&self) -> GenerationId {
+        GenerationId(self.current_gen)
     }
 
     /// Gets the IdGen's current generation
@@ -82,7 +205,14 @@ impl IdGen {
 
 impl Clone for IdGen {
     fn clone(&self) -> Self {
-        Self::new()
+        if self.fixed {
+            IdGen {
+                current_gen: self.current_gen,
+                fixed: true,
+            }
+        } else {
+            Self::new()
+        }
     }
 }
 
@@ -137,6 +267,38 @@ mod test {
         assert_lt!(b1.generation, c1.generation);
     }
 
+    #[test]
+    fn test_fixed() {
+        let mut a = IdGen::new_fixed();
+        let a_first = a.create_id(1);
+        a.next_gen();
+        let b = a.clone();
+
+        assert!(a.is_fixed() && b.is_fixed());
+        assert_eq!(a.create_id(1), a_first);
+        assert_eq!(b.create_id(1), a_first);
+
+        let c = b.fresh();
+        assert!(c.is_fixed());
+        assert_ne!(c.create_id(1), a_first);
+        assert!(!IdGen::new().fresh().is_fixed());
+    }
+
+    #[test]
+    fn test_ord() {
+        let a = IdGen::new();
+        let mut b = a.clone();
+        b.next_gen();
+
+        assert_lt!(a.create_id(1), a.create_id(2));
+        assert_lt!(a.create_id(1), b.create_id(1));
+        assert_lt!(b.create_id(1), a.create_id(2));
+
+        let mut ids = vec![b.create_id(0), a.create_id(1), a.create_id(0)];
+        ids.sort();
+        assert_eq!(ids, vec![a.create_id(0), b.create_id(0), a.create_id(1)]);
+    }
+
     fn assert_same_index(id1: Id, id2: Id) {
         let a: usize = id1.index();
         let b: usize = id2.index();
@@ -10,11 +10,19 @@
 //! PGraph an O(1) operation, at the cost of slighlly more expensive accesses and modifications. Methods that modify the
 //! graph clone the `PGraph` by default and return the new, modified version. There are also methods that modify the graph in-place.
 
+#[macro_use]
+mod macros;
+
 mod id;
 mod pgraph;
 
-pub use crate::id::Id;
-pub use crate::pgraph::{Edge, PGraph, Vertex};
+#[cfg(any(test, feature = "test-util"))]
+pub use crate::id::reset_generation_counter_for_tests;
+pub use crate::id::{GenerationId, Id};
+pub use crate::pgraph::{
+    ConnectError, CycleError, DecodeError, Edge, EdgeUpsert, FilteredView, GraphDiff,
+    OccupiedError, PGraph, RemovedVertex, StructuralStats, Undirected, ValidationError, Vertex,
+};
 
 #[cfg(test)]
 mod tests;
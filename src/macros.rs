@@ -0,0 +1,67 @@
+// Copyright 2018 Weston Carvalho
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Macros for writing small `PGraph`s as literals.
+
+/// Builds a `PGraph` from a literal description and binds it, along with the [Id](struct.Id.html)s of its vertices, to local variables.
+///
+/// The macro takes the name of the graph, a list of `name: data` vertices, and a list of `source -> sink: weight` edges,
+/// separated by semicolons. The graph name may be prefixed with `mut` to keep the graph mutable and may be followed by a
+/// type annotation. Every vertex name becomes a local variable holding that vertex's `Id`, so an edge that refers to an
+/// undeclared vertex is rejected at compile time.
+/// # Examples
+///
+/// ```
+/// # use pgraph::{pgraph, PGraph};
+/// # fn main() {
+/// pgraph! { g; a: 1, b: 2, c: 3; a -> b: 12, b -> c: 23, c -> a: 31 }
+///
+/// assert_eq!(g.ids().count(), 3);
+/// assert_eq!(g[(a,)], 1);
+/// assert_eq!(g.weight(a, b), Some(&12));
+/// assert_eq!(g.weight(c, a), Some(&31));
+/// assert!(!g.has_edge(a, c));
+///
+/// pgraph! { mut h: PGraph<&str, ()>; x: "x", y: "y"; }
+/// h.connect_mut(x, y, ());
+/// assert!(h.has_edge(x, y));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use pgraph::pgraph;
+/// # fn main() {
+/// pgraph! { g; a: 1; a -> b: 12 }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! pgraph {
+    (mut $graph:ident; $($rest:tt)*) => {
+        $crate::pgraph!(@build $graph, $crate::PGraph<_, _>; $($rest)*);
+    };
+    (mut $graph:ident : $graph_type:ty; $($rest:tt)*) => {
+        $crate::pgraph!(@build $graph, $graph_type; $($rest)*);
+    };
+    ($graph:ident; $($rest:tt)*) => {
+        $crate::pgraph!(@build $graph, $crate::PGraph<_, _>; $($rest)*);
+        let $graph = $graph;
+    };
+    ($graph:ident : $graph_type:ty; $($rest:tt)*) => {
+        $crate::pgraph!(@build $graph, $graph_type; $($rest)*);
+        let $graph = $graph;
+    };
+    (@build $graph:ident, $graph_type:ty;
+        $($vertex:ident : $data:expr),* $(,)?;
+        $($source:ident -> $sink:ident : $weight:expr),* $(,)?
+    ) => {
+        #[allow(unused_mut)]
+        let mut $graph: $graph_type = $crate::PGraph::new();
+        $(let $vertex = $graph.add_mut($data);)*
+        $($graph.connect_mut($source, $sink, $weight);)*
+    };
+}
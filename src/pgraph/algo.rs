@@ -0,0 +1,597 @@
+//! Graph algorithms implemented directly on `PGraph`, built on top of `petgraph`'s traversals.
+
+use super::{CycleError, Id, PGraph};
+use petgraph::algo::toposort;
+use petgraph::visit::{Bfs, Reversed};
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ops::Add;
+
+impl<V, E> PGraph<V, E> {
+    /// Returns the [Id](struct.Id.html)s of every vertex that can be reached from `from` by following one or more
+    /// outbound edges.
+    ///
+    /// `from` itself is only included if there's a path from it back to itself (i.e. it's part of a cycle or has a
+    /// self-loop). If `from` isn't in the PGraph, the set is empty.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # use std::collections::HashSet;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// let id4 = g.add_mut(4);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    /// g.connect_mut(id3, id2, 32);
+    /// g.connect_mut(id4, id1, 41);
+    ///
+    /// let expected: HashSet<_> = vec![id2, id3].into_iter().collect();
+    /// assert_eq!(g.reachable(id1), expected);
+    ///
+    /// let expected: HashSet<_> = vec![id2, id3].into_iter().collect();
+    /// assert_eq!(g.reachable(id2), expected);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reachable<T: Borrow<Id>>(&self, from: T) -> HashSet<Id> {
+        let from = *from.borrow();
+        let mut result = HashSet::new();
+
+        let mut bfs = Bfs::new(self, from);
+        // The traversal always starts by visiting `from` itself.
+        bfs.next(self);
+        while let Some(id) = bfs.next(self) {
+            result.insert(id);
+        }
+
+        if self
+            .predecessor_ids(from)
+            .any(|source| source == from || result.contains(&source))
+        {
+            result.insert(from);
+        }
+        result
+    }
+
+    /// Returns the [Id](struct.Id.html)s of every vertex that `id` depends on, directly or indirectly: every vertex that can be
+    /// reached from `id` by following one or more outbound edges. This is the same as [`reachable`](struct.PGraph.html#method.reachable).
+    ///
+    /// `id` itself is only included if it's part of a cycle or has a self-loop. If `id` isn't in the PGraph, the set is empty.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # use std::collections::HashSet;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    ///
+    /// let expected: HashSet<_> = vec![id2, id3].into_iter().collect();
+    /// assert_eq!(g.descendants(id1), expected);
+    /// assert!(g.descendants(id3).is_empty());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn descendants<T: Borrow<Id>>(&self, id: T) -> HashSet<Id> {
+        self.reachable(id)
+    }
+
+    /// Returns the [Id](struct.Id.html)s of every vertex that depends on `id`, directly or indirectly: every vertex from which `id`
+    /// can be reached by following one or more outbound edges. (This is `descendants` with the edges reversed.)
+    ///
+    /// `id` itself is only included if it's part of a cycle or has a self-loop. If `id` isn't in the PGraph, the set is empty.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # use std::collections::HashSet;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    /// g.connect_mut(id3, id3, 33);
+    ///
+    /// let expected: HashSet<_> = vec![id1, id2, id3].into_iter().collect();
+    /// assert_eq!(g.ancestors(id3), expected);
+    /// assert!(g.ancestors(id1).is_empty());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ancestors<T: Borrow<Id>>(&self, id: T) -> HashSet<Id> {
+        let to = *id.borrow();
+        let mut result = HashSet::new();
+
+        let reversed = Reversed(self);
+        let mut bfs = Bfs::new(reversed, to);
+        // The traversal always starts by visiting `to` itself.
+        bfs.next(reversed);
+        while let Some(id) = bfs.next(reversed) {
+            result.insert(id);
+        }
+
+        if self
+            .outbound_ids(to)
+            .any(|sink| sink == to || result.contains(&sink))
+        {
+            result.insert(to);
+        }
+        result
+    }
+
+    /// Returns the [Id](struct.Id.html)s of every vertex that one of the vertices in `frontier` has an edge to, with no duplicates.
+    /// This is one step of a level-by-level breadth-first search from several sources at once. Ids in `frontier` that aren't in the
+    /// PGraph are skipped.
+    ///
+    /// The frontier's own vertices are only included if there's an edge to them from somewhere in the frontier.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # use std::collections::HashSet;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// let id4 = g.add_mut(4);
+    ///
+    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id2, id3, 23);
+    /// g.connect_mut(id2, id4, 24);
+    ///
+    /// let expected: HashSet<_> = vec![id3, id4].into_iter().collect();
+    /// assert_eq!(g.expand_frontier(vec![id1, id2]), expected);
+    /// assert!(g.expand_frontier(expected).is_empty());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn expand_frontier<I: IntoIterator<Item = Id>>(&self, frontier: I) -> HashSet<Id> {
+        frontier
+            .into_iter()
+            .flat_map(|id| self.outbound_ids(id))
+            .collect()
+    }
+
+    /// Returns the eccentricity of the vertex `id`: the greatest number of edges that has to be followed to get
+    /// from `id` to any vertex it can reach.
+    ///
+    /// Vertices that can't be reached from `id` are ignored, so in a disconnected PGraph this is the eccentricity
+    /// within the part of the PGraph `id` can reach. A vertex that can't reach anything has an eccentricity of 0.
+    /// Returns `None` if `id` isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    ///
+    /// assert_eq!(g.eccentricity(id1), Some(2));
+    /// assert_eq!(g.eccentricity(id2), Some(1));
+    /// assert_eq!(g.eccentricity(id3), Some(0));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn eccentricity<T: Borrow<Id>>(&self, id: T) -> Option<usize> {
+        let id = id.borrow();
+        if self.has_vertex(id) {
+            self.hop_distances(*id).values().cloned().max()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the diameter of the PGraph: the largest [eccentricity](struct.PGraph.html#method.eccentricity) of
+    /// any of its vertices.
+    ///
+    /// Returns `None` if the PGraph is empty, or if there's some vertex that can't reach every other vertex
+    /// (in which case the diameter is effectively infinite).
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// assert_eq!(g.diameter(), None);
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    /// assert_eq!(g.diameter(), None);
+    ///
+    /// g.connect_mut(id3, id1, 31);
+    /// assert_eq!(g.diameter(), Some(2));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn diameter(&self) -> Option<usize> {
+        let vertex_count = self.count_vertices();
+        let mut result = None;
+
+        for id in self.ids() {
+            let distances = self.hop_distances(id);
+            if distances.len() != vertex_count {
+                return None;
+            }
+            result = distances.values().cloned().max().max(result);
+        }
+        result
+    }
+
+    /// Finds the core number of every vertex in the PGraph: the largest `k` such that the vertex is in the `k`-core (see `k_core`).
+    ///
+    /// Edge directions are ignored, so a vertex's degree is the number of other vertices it has an edge to or from. Self-loops
+    /// don't count towards a vertex's degree.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4, 5]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[2], ids[0], 31);
+    /// g.connect_mut(ids[3], ids[2], 43);
+    ///
+    /// let cores = g.core_number();
+    /// assert_eq!(cores[&ids[0]], 2);
+    /// assert_eq!(cores[&ids[2]], 2);
+    /// assert_eq!(cores[&ids[3]], 1);
+    /// assert_eq!(cores[&ids[4]], 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn core_number(&self) -> HashMap<Id, usize> {
+        let neighbors: HashMap<Id, HashSet<Id>> = self
+            .ids()
+            .map(|id| {
+                let others = self.neighbors_undirected(id).filter(|&other| other != id);
+                (id, others.collect())
+            })
+            .collect();
+        let mut degrees: HashMap<Id, usize> = neighbors
+            .iter()
+            .map(|(&id, others)| (id, others.len()))
+            .collect();
+        let mut heap: BinaryHeap<_> = degrees
+            .iter()
+            .map(|(&id, &degree)| Reverse((degree, id)))
+            .collect();
+
+        // Peel off the vertex with the lowest remaining degree, skipping stale heap entries for vertices whose degree has dropped.
+        let mut result = HashMap::new();
+        let mut core = 0;
+        while let Some(Reverse((degree, id))) = heap.pop() {
+            if result.contains_key(&id) || degree != degrees[&id] {
+                continue;
+            }
+            core = core.max(degree);
+            result.insert(id, core);
+            for other in &neighbors[&id] {
+                if !result.contains_key(other) {
+                    let degree = degrees
+                        .get_mut(other)
+                        .expect("Every neighbor is in the PGraph.");
+                    *degree -= 1;
+                    heap.push(Reverse((*degree, *other)));
+                }
+            }
+        }
+        result
+    }
+
+    /// Finds the number of edges on the shortest path from `from` to every vertex it can reach (including itself,
+    /// at a distance of 0).
+    fn hop_distances(&self, from: Id) -> HashMap<Id, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        distances.insert(from, 0);
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            let next_distance = distances[&current] + 1;
+            for neighbor in self.outbound_ids(current) {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(next_distance);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        distances
+    }
+}
+
+impl<V: Clone, E: Clone + Default> PGraph<V, E> {
+    /// Returns the complement of this PGraph: a PGraph with the same vertices, where there's an edge from one vertex
+    /// to another exactly when there wasn't one in this PGraph. The new edges have a weight of `E::default()`.
+    ///
+    /// The complement never has any self-loops (even if this PGraph had some);
+    /// use [`complement_with_self_loops`](struct.PGraph.html#method.complement_with_self_loops) to complement
+    /// those as well. The vertices keep their [Id](struct.Id.html)s, so Ids from this PGraph work on the complement.
+    /// The complement of a PGraph created by `dag_checked` doesn't reject cycles, since it almost always has some.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id1, 11);
+    ///
+    /// let c = g.complement();
+    /// assert_eq!(c.edge_count(), 1);
+    /// assert_eq!(c[(id2, id1)], 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn complement(&self) -> Self {
+        self.complement_impl(false)
+    }
+
+    /// Returns the complement of this PGraph, like [`complement`](struct.PGraph.html#method.complement), except
+    /// that self-loops are complemented too: every vertex without a self-loop gets one, and vice versa.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id1, 11);
+    ///
+    /// let c = g.complement_with_self_loops();
+    /// assert_eq!(c.edge_count(), 2);
+    /// assert!(c.has_edge(id2, id1));
+    /// assert!(c.has_edge(id2, id2));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn complement_with_self_loops(&self) -> Self {
+        self.complement_impl(true)
+    }
+
+    fn complement_impl(&self, self_loops: bool) -> Self {
+        let mut result = self.clone();
+        result.acyclic = false;
+        let ids: Vec<_> = self.ids().collect();
+
+        for &source in &ids {
+            for &sink in &ids {
+                if self.has_edge(source, sink) || (source == sink && !self_loops) {
+                    result.disconnect_mut(source, sink);
+                } else {
+                    result.connect_mut(source, sink, E::default());
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<V, E: Add<Output = E> + Clone + Default> PGraph<V, E> {
+    /// Returns the sum of the weights of the edges between each pair of consecutive vertices in `path`, or `None` if any of
+    /// those pairs aren't connected. A path with fewer than two vertices has no edges, so it costs `E::default()`, unless its
+    /// vertex isn't in the PGraph.
+    ///
+    /// This is useful for scoring paths found by algorithms that ignore weights, like `petgraph`'s `all_simple_paths`.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    ///
+    /// assert_eq!(g.path_cost(&[id1, id2, id3]), Some(35));
+    /// assert_eq!(g.path_cost(&[id1, id3]), None);
+    /// assert_eq!(g.path_cost(&[id2]), Some(0));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path_cost<T: Borrow<Id>, I: IntoIterator<Item = T>>(&self, path: I) -> Option<E> {
+        let mut path = path.into_iter();
+        let mut source = match path.next() {
+            Some(id) => *id.borrow(),
+            None => return Some(E::default()),
+        };
+        if !self.has_vertex(source) {
+            return None;
+        }
+
+        let mut total = E::default();
+        for sink in path {
+            let sink = *sink.borrow();
+            total = total + self.weight(source, sink)?.clone();
+            source = sink;
+        }
+        Some(total)
+    }
+}
+
+impl<V, E: Add<Output = E> + Clone + Ord> PGraph<V, E> {
+    /// Runs Dijkstra's algorithm from `start` and returns the whole shortest-path tree: for each vertex reachable from `start`, the
+    /// previous vertex on a shortest path to it and the total weight of that path, as (predecessor: Id, cost: E).
+    /// Any shortest path can be rebuilt by following the predecessors back to `start`.
+    ///
+    /// `start` itself isn't in the map, since its shortest path has no edges, and neither are vertices it can't reach. If `start`
+    /// isn't in the PGraph, the map is empty. Ties between equally short paths are broken consistently, but arbitrarily.
+    /// Assumes that adding a weight never makes a path cheaper (e.g. no negative weights).
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 1);
+    /// g.connect_mut(ids[1], ids[2], 1);
+    /// g.connect_mut(ids[0], ids[2], 5);
+    /// g.connect_mut(ids[2], ids[0], 1);
+    ///
+    /// let tree = g.shortest_path_tree(ids[0]);
+    /// assert_eq!(tree.len(), 2);
+    /// assert_eq!(tree[&ids[1]], (ids[0], 1));
+    /// assert_eq!(tree[&ids[2]], (ids[1], 2));
+    /// assert!(!tree.contains_key(&ids[3]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn shortest_path_tree<T: Borrow<Id>>(&self, start: T) -> HashMap<Id, (Id, E)> {
+        let start = *start.borrow();
+        let mut result = HashMap::new();
+        let mut heap: BinaryHeap<_> = self
+            .outbound_edges(start)
+            .map(|(_, sink, weight)| Reverse((weight.clone(), sink, start)))
+            .collect();
+
+        while let Some(Reverse((cost, id, predecessor))) = heap.pop() {
+            if id == start || result.contains_key(&id) {
+                continue;
+            }
+            for (_, sink, weight) in self.outbound_edges(id) {
+                if sink != start && !result.contains_key(&sink) {
+                    heap.push(Reverse((cost.clone() + weight.clone(), sink, id)));
+                }
+            }
+            result.insert(id, (predecessor, cost));
+        }
+        result
+    }
+}
+
+impl<V: Clone, E: Clone> PGraph<V, E> {
+    /// Returns the `k`-core of this PGraph: what's left after repeatedly removing every vertex with fewer than `k` neighbors, until
+    /// there are none left. Like `core_number`, this ignores edge directions and self-loops. The remaining vertices keep their
+    /// [Id](struct.Id.html)s and slots, so Ids from this PGraph work on the result.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[2], ids[0], 31);
+    /// g.connect_mut(ids[3], ids[2], 43);
+    ///
+    /// let core = g.k_core(2);
+    /// assert_eq!(core.ids().collect::<Vec<_>>(), ids[..3].to_vec());
+    /// assert_eq!(core.edge_count(), 3);
+    /// assert_eq!(g.k_core(3).ids().count(), 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn k_core(&self, k: usize) -> Self {
+        let outside: Vec<Id> = self
+            .core_number()
+            .into_iter()
+            .filter(|&(_, core)| core < k)
+            .map(|(id, _)| id)
+            .collect();
+        self.remove_all(outside)
+    }
+
+    /// Returns the transitive reduction of this PGraph: a copy of it without the edges that are implied by longer paths, i.e. every
+    /// edge from `a` to `b` where `b` can also be reached from `a` some other way. The result has the fewest edges of any PGraph with
+    /// the same reachability, and its vertices keep their [Id](struct.Id.html)s, so Ids from this PGraph work on it.
+    ///
+    /// The transitive reduction is only unique for directed acyclic graphs, so this returns a [CycleError](struct.CycleError.html)
+    /// if the PGraph has a cycle (including a self-loop).
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    /// g.connect_mut(id1, id3, 13);
+    ///
+    /// let reduced = g.transitive_reduction().unwrap();
+    /// assert_eq!(reduced.edge_count(), 2);
+    /// assert!(!reduced.has_edge(id1, id3));
+    ///
+    /// g.connect_mut(id3, id1, 31);
+    /// assert!(g.transitive_reduction().is_err());
+    /// # }
+    /// ```
+    pub fn transitive_reduction(&self) -> Result<Self, CycleError> {
+        let order = toposort(self, None).map_err(|cycle| CycleError {
+            vertex: cycle.node_id(),
+        })?;
+        if let Some((vertex, _)) = self.self_loops().next() {
+            return Err(CycleError { vertex });
+        }
+
+        // Work backwards through the topological order, so each vertex's successors already know their descendants.
+        let mut descendants: HashMap<Id, HashSet<Id>> = HashMap::new();
+        let mut result = self.clone();
+        for &source in order.iter().rev() {
+            let mut reachable = HashSet::new();
+            for sink in self.outbound_ids(source) {
+                reachable.extend(descendants[&sink].iter().cloned());
+            }
+            for sink in self.outbound_ids(source) {
+                if reachable.contains(&sink) {
+                    result.disconnect_mut(source, sink);
+                }
+            }
+            reachable.extend(self.outbound_ids(source));
+            descendants.insert(source, reachable);
+        }
+        Ok(result)
+    }
+}
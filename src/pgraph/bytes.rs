@@ -0,0 +1,166 @@
+//! A compact binary format for `PGraph`s: a table of vertices, then a table of edges that refer to the vertices by position.
+
+use super::{DecodeError, Id, PGraph};
+
+/// Starts every encoded PGraph. The last byte is the version of the format.
+const HEADER: &[u8] = b"PGR\x01";
+
+impl<V: AsRef<[u8]>, E: AsRef<[u8]>> PGraph<V, E> {
+    /// Encodes this PGraph in a compact binary format that can be read back with [`from_bytes`](struct.PGraph.html#method.from_bytes).
+    ///
+    /// The encoding starts with a table of the vertices' data, in iteration order, followed by each vertex's outbound edges, whose
+    /// sinks are stored as positions in the vertex table instead of [Id](struct.Id.html)s. Every count, length, and position is a
+    /// variable-length integer, so small PGraphs stay small. The data and weights are written as the bytes they return from `as_ref`.
+    ///
+    /// Only the topology, data, and weights are kept: Ids, empty slots, and the modes the PGraph was created with (like
+    /// `with_reverse_index`) aren't.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<Vec<u8>, Vec<u8>>::new();
+    /// let id1 = g.add_mut(b"one".to_vec());
+    /// let id2 = g.add_mut(b"two".to_vec());
+    /// g.connect_mut(id1, id2, vec![12]);
+    ///
+    /// let bytes = g.to_bytes();
+    /// let h = PGraph::<Vec<u8>, Vec<u8>>::from_bytes(&bytes).unwrap();
+    ///
+    /// let ids: Vec<_> = h.ids().collect();
+    /// assert_eq!(h[(ids[0],)], b"one");
+    /// assert_eq!(h[(ids[0], ids[1])], vec![12]);
+    /// assert!(!h.has_vertex(id1));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let ranks = self.ranks();
+        let mut bytes = HEADER.to_vec();
+
+        write_len(&mut bytes, self.count_vertices());
+        for vertex in self {
+            write_bytes(&mut bytes, vertex.data().as_ref());
+        }
+        for vertex in self {
+            write_len(&mut bytes, vertex.len_neighbors());
+            for (sink, weight) in vertex {
+                write_len(&mut bytes, ranks[sink.index()]);
+                write_bytes(&mut bytes, weight.as_ref());
+            }
+        }
+        bytes
+    }
+}
+
+impl<V: Clone + From<Vec<u8>>, E: From<Vec<u8>>> PGraph<V, E> {
+    /// Decodes a PGraph written by [`to_bytes`](struct.PGraph.html#method.to_bytes). Each vertex's data and each edge's weight
+    /// is created from the bytes that were written for it.
+    ///
+    /// The result is a new PGraph with a fresh generation, so none of the original PGraph's [Id](struct.Id.html)s work on it.
+    /// Its vertices are in the same order as the original's, so the new Ids can be matched up with the old ones through `ids`.
+    ///
+    /// Returns a [DecodeError](enum.DecodeError.html) if `bytes` isn't a whole PGraph in this format.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::{DecodeError, PGraph};
+    /// # fn main() {
+    /// let mut g = PGraph::<Vec<u8>, Vec<u8>>::new();
+    /// let id = g.add_mut(b"loop".to_vec());
+    /// g.connect_mut(id, id, vec![]);
+    /// let bytes = g.to_bytes();
+    ///
+    /// let h = PGraph::<Vec<u8>, Vec<u8>>::from_bytes(&bytes).unwrap();
+    /// assert_eq!(h.edge_count(), 1);
+    ///
+    /// let truncated = PGraph::<Vec<u8>, Vec<u8>>::from_bytes(&bytes[..bytes.len() - 1]);
+    /// assert_eq!(truncated.unwrap_err(), DecodeError::UnexpectedEnd);
+    /// # }
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if !bytes.starts_with(HEADER) {
+            return Err(DecodeError::BadHeader);
+        }
+        let mut reader = Reader {
+            bytes: &bytes[HEADER.len()..],
+        };
+
+        let mut graph = Self::new();
+        let count = reader.len()?;
+        let mut ids: Vec<Id> = Vec::new();
+        for _ in 0..count {
+            ids.push(graph.add_mut(reader.bytes()?.into()));
+        }
+        for &source in &ids {
+            for _ in 0..reader.len()? {
+                let sink = reader.len()?;
+                let sink = *ids.get(sink).ok_or(DecodeError::SinkOutOfRange {
+                    sink,
+                    vertices: count,
+                })?;
+                graph.connect_mut(source, sink, reader.bytes()?.into());
+            }
+        }
+
+        match reader.bytes.len() {
+            0 => Ok(graph),
+            extra => Err(DecodeError::TrailingBytes { extra }),
+        }
+    }
+}
+
+/// Appends `len` as an unsigned LEB128 integer: seven bits per byte, least significant first, with the high bit set on every byte but the last.
+fn write_len(bytes: &mut Vec<u8>, mut len: usize) {
+    while len >= 0x80 {
+        bytes.push((len & 0x7f) as u8 | 0x80);
+        len >>= 7;
+    }
+    bytes.push(len as u8);
+}
+
+/// Appends `data`, preceded by its length.
+fn write_bytes(bytes: &mut Vec<u8>, data: &[u8]) {
+    write_len(bytes, data.len());
+    bytes.extend_from_slice(data);
+}
+
+/// Reads the values written by `write_len` and `write_bytes` off the front of a slice.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let (&first, rest) = self.bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        self.bytes = rest;
+        Ok(first)
+    }
+
+    fn len(&mut self) -> Result<usize, DecodeError> {
+        let mut len: usize = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as usize;
+            if shift >= usize::BITS || (bits << shift) >> shift != bits {
+                return Err(DecodeError::Overflow);
+            }
+            len |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(len);
+            }
+            shift += 7;
+        }
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>, DecodeError> {
+        let len = self.len()?;
+        if len > self.bytes.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (data, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(data.to_vec())
+    }
+}
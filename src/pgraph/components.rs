@@ -0,0 +1,315 @@
+//! Connected-component queries, optionally backed by an incrementally maintained disjoint-set forest.
+
+use super::{Id, PGraph};
+use im::Vector;
+use petgraph::visit::IntoEdgeReferences;
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// The components a PGraph created by `with_union_find` keeps track of. Removing a vertex or an edge empties the cache instead of
+/// fixing the sets up, since union-find can't split a set; the next query rebuilds them from the PGraph.
+pub(super) type ComponentCache = OnceLock<DisjointSets>;
+
+/// A disjoint-set (union-find) forest over the slots of a PGraph, where two slots are in the same set iff their
+/// vertices are weakly connected. Slots that have never held a vertex are singletons that aren't counted.
+#[derive(Clone, Debug, Default)]
+pub(super) struct DisjointSets {
+    parents: Vector<usize>,
+    sizes: Vector<usize>,
+    count: usize,
+}
+
+impl DisjointSets {
+    /// Creates the sets for `graph` from scratch.
+    fn from_graph<V, E>(graph: &PGraph<V, E>) -> Self {
+        let mut sets = Self::default();
+        for id in graph.ids() {
+            sets.add(id.index());
+        }
+        for (source, sink, _) in graph.edges() {
+            sets.union(source.index(), sink.index());
+        }
+        sets
+    }
+
+    /// Puts the slot at `index` into a new set of its own.
+    pub(super) fn add(&mut self, index: usize) {
+        while self.parents.len() <= index {
+            self.parents.push_back(self.parents.len());
+            self.sizes.push_back(1);
+        }
+        self.parents.set(index, index);
+        self.sizes.set(index, 1);
+        self.count += 1;
+    }
+
+    /// Merges the sets containing the slots at `a` and `b`.
+    pub(super) fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            // Hang the smaller tree under the larger one, so trees stay O(log V) deep.
+            let (root, child) = if self.sizes[a] < self.sizes[b] {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            self.parents.set(child, root);
+            self.sizes.set(root, self.sizes[root] + self.sizes[child]);
+            self.count -= 1;
+        }
+    }
+
+    fn find(&self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            index = self.parents[index];
+        }
+        index
+    }
+}
+
+impl<V, E> PGraph<V, E> {
+    /// Creates a new, empty PGraph that keeps track of its weakly connected components as vertices are added and connected,
+    /// so [`same_component`](struct.PGraph.html#method.same_component) takes O(log V) time and
+    /// [`component_count`](struct.PGraph.html#method.component_count) takes O(1) time.
+    ///
+    /// This is meant for PGraphs that mostly grow. Adding vertices and edges keeps the components up to date cheaply. Removing a
+    /// vertex or an edge only marks them as stale, and the next query rebuilds them from scratch in O(V + E) time, so a batch of
+    /// removals costs one rebuild. Getting a [Vertex](struct.Vertex.html) mutably (with `vertex_mut`, `vertices_mut`, or `IndexMut`)
+    /// marks them as stale too, since its edges could be removed through it.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::with_union_find();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// assert!(g.has_union_find());
+    /// assert_eq!(g.component_count(), 3);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// assert_eq!(g.component_count(), 2);
+    /// assert!(g.same_component(ids[1], ids[0]));
+    /// assert!(!g.same_component(ids[0], ids[2]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_union_find() -> Self {
+        let mut graph = Self::new();
+        graph.components = Some(DisjointSets::default().into());
+        graph
+    }
+
+    /// Returns `true` iff this PGraph keeps track of its connected components. (See `with_union_find`.)
+    #[must_use]
+    pub fn has_union_find(&self) -> bool {
+        self.components.is_some()
+    }
+
+    /// Returns `true` iff `a` and `b` are both in the PGraph and in the same weakly connected component, i.e. there's a path
+    /// between them when the direction of the edges is ignored. Every vertex is in the same component as itself.
+    ///
+    /// This takes O(log V) time if the PGraph was created by `with_union_find` (after rebuilding its components, if a vertex or an
+    /// edge has been removed since the last query), and O(V + E) time otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[2], ids[1], 32);
+    /// assert!(g.same_component(ids[0], ids[2]));
+    ///
+    /// g.disconnect_mut(ids[2], ids[1]);
+    /// assert!(!g.same_component(ids[0], ids[2]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn same_component<T: Borrow<Id>>(&self, a: T, b: T) -> bool {
+        let (a, b) = (a.borrow(), b.borrow());
+        if !self.has_vertex(a) || !self.has_vertex(b) {
+            return false;
+        }
+
+        match self.cached_components() {
+            Some(sets) => sets.find(a.index()) == sets.find(b.index()),
+            None => {
+                let sets = DisjointSets::from_graph(self);
+                sets.find(a.index()) == sets.find(b.index())
+            }
+        }
+    }
+
+    /// Counts the weakly connected components of the PGraph (see `same_component`). An empty PGraph has none.
+    ///
+    /// This takes O(1) time if the PGraph was created by `with_union_find` (after rebuilding its components, as in `same_component`),
+    /// and O(V + E) time otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// assert_eq!(g.component_count(), 0);
+    ///
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[3], ids[2], 43);
+    /// assert_eq!(g.component_count(), 2);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        match self.cached_components() {
+            Some(sets) => sets.count,
+            None => DisjointSets::from_graph(self).count,
+        }
+    }
+
+    /// Returns `true` iff the PGraph is a forest when the direction of its edges is ignored, i.e. there's no cycle in it, treating a
+    /// pair of vertices with edges in both directions as a single undirected edge. A self-loop counts as a cycle.
+    ///
+    /// An empty PGraph is a forest (with no trees in it). Uses `component_count`, so this takes O(V + E) time either way.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[1], ids[0], 21);
+    /// g.connect_mut(ids[2], ids[1], 32);
+    /// assert!(g.is_forest());
+    ///
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// assert!(!g.is_forest());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_forest(&self) -> bool {
+        if self.self_loops().next().is_some() {
+            return false;
+        }
+
+        let undirected_edges = self.as_undirected().edge_references().count();
+        undirected_edges + self.component_count() == self.count_vertices()
+    }
+
+    /// Returns `true` iff the PGraph is a tree when the direction of its edges is ignored, i.e. it's a forest (see `is_forest`)
+    /// with exactly one component. An empty PGraph isn't a tree.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// assert!(!g.is_tree());
+    ///
+    /// g.connect_mut(ids[2], ids[0], 31);
+    /// assert!(g.is_tree());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_tree(&self) -> bool {
+        self.component_count() == 1 && self.is_forest()
+    }
+
+    /// Returns the PGraph's components if it keeps track of them, first rebuilding them if they're stale.
+    fn cached_components(&self) -> Option<&DisjointSets> {
+        let cache = self.components.as_ref()?;
+        Some(cache.get_or_init(|| DisjointSets::from_graph(self)))
+    }
+
+    /// Puts the new vertex in the slot at `index` into a component of its own, if this PGraph keeps track of its components
+    /// and they aren't stale.
+    pub(super) fn track_component(&mut self, index: usize) {
+        if let Some(sets) = self.components.as_mut().and_then(ComponentCache::get_mut) {
+            sets.add(index);
+        }
+    }
+
+    /// Merges the components of `source` and `sink` after an edge is created between them, if this PGraph keeps track of its
+    /// components and they aren't stale.
+    pub(super) fn join_components(&mut self, source: Id, sink: Id) {
+        if let Some(sets) = self.components.as_mut().and_then(ComponentCache::get_mut) {
+            sets.union(source.index(), sink.index());
+        }
+    }
+
+    /// Marks the PGraph's components as stale after a vertex or edge may have been removed, if it keeps track of them.
+    /// They're rebuilt by the next query.
+    pub(super) fn invalidate_components(&mut self) {
+        if let Some(cache) = &mut self.components {
+            cache.take();
+        }
+    }
+}
+
+impl<V: Clone, E: Clone> PGraph<V, E> {
+    /// Removes the weakly connected component containing `seed` (see `same_component`) from the PGraph and returns it as a new
+    /// PGraph, along with a map from each removed [Id](struct.Id.html) to the corresponding [Id](struct.Id.html) in the new PGraph.
+    /// Returns `None` (and leaves the PGraph alone) if `seed` isn't in the PGraph.
+    ///
+    /// The new PGraph keeps the component's vertices in the same relative order, and keeps a reverse index or its components
+    /// iff this one does. Because vertices are removed, this PGraph moves to a new generation.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[3], ids[2], 43);
+    ///
+    /// let (h, id_map) = g.split_component_mut(ids[2]).unwrap();
+    /// assert_eq!(h.iter_data().cloned().collect::<Vec<_>>(), vec![1, 3, 4]);
+    /// assert_eq!(h.weight(id_map[&ids[3]], id_map[&ids[2]]), Some(&43));
+    /// assert_eq!(g.ids().collect::<Vec<_>>(), vec![ids[1]]);
+    ///
+    /// assert!(g.split_component_mut(ids[0]).is_none());
+    /// # }
+    /// ```
+    pub fn split_component_mut<T: Borrow<Id>>(
+        &mut self,
+        seed: T,
+    ) -> Option<(Self, HashMap<Id, Id>)> {
+        let seed = *seed.borrow();
+        if !self.has_vertex(seed) {
+            return None;
+        }
+
+        let mut component = HashSet::new();
+        component.insert(seed);
+        let mut stack = vec![seed];
+        while let Some(id) = stack.pop() {
+            for neighbor in self.neighbors_undirected(id) {
+                if component.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        let mut result = self.new_like();
+        let mut ids = HashMap::new();
+        for v in self.into_iter().filter(|v| component.contains(&v.id())) {
+            ids.insert(v.id(), result.add_mut(v.data().clone()));
+        }
+        for (source, sink, weight) in self.edges() {
+            if component.contains(&source) {
+                result.connect_unchecked_mut(ids[&source], ids[&sink], weight.clone());
+            }
+        }
+
+        self.remove_all_mut(&component);
+        Some((result, ids))
+    }
+}
@@ -0,0 +1,127 @@
+use super::{Id, PGraph};
+
+/// The differences between two versions of a PGraph, as created by [`diff`](struct.PGraph.html#method.diff).
+///
+/// Vertices and edges are identified by [Id](struct.Id.html), so a diff is only meaningful between PGraphs that
+/// share history (i.e. one was derived from the other by cloning and modifying it).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    /// Vertices that only exist in the newer PGraph.
+    pub added_vertices: Vec<Id>,
+    /// Vertices that only exist in the older PGraph.
+    pub removed_vertices: Vec<Id>,
+    /// Edges, as `(source, sink)`, that only exist in the newer PGraph.
+    pub added_edges: Vec<(Id, Id)>,
+    /// Edges, as `(source, sink)`, that only exist in the older PGraph.
+    /// This includes the edges that were removed along with one of their vertices.
+    pub removed_edges: Vec<(Id, Id)>,
+    /// Edges, as `(source, sink)`, that exist in both PGraphs but have different weights.
+    pub changed_edges: Vec<(Id, Id)>,
+}
+
+impl GraphDiff {
+    /// Returns `true` iff the two PGraphs had no differences.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl<V, E> PGraph<V, E> {
+    /// Returns every edge, as `(source, sink)`, that's in this PGraph but not in `other`, in the same order as `edges`.
+    /// Weights aren't compared, so this doesn't need `E: PartialEq`. (It's the same as the `removed_edges` of `self.diff(other)`.)
+    ///
+    /// Edges are matched up by [Id](struct.Id.html), so this only makes sense when one of the PGraphs was derived from the other.
+    /// Otherwise, they have no Ids in common, and every edge in this PGraph is reported.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut old = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = old.add_all_mut(vec![1usize, 2, 3]);
+    /// old.connect_mut(ids[0], ids[1], 12);
+    /// old.connect_mut(ids[1], ids[2], 23);
+    ///
+    /// let mut new = old.clone();
+    /// new.disconnect_mut(ids[0], ids[1]);
+    /// new.connect_mut(ids[2], ids[0], 31);
+    /// new.connect_mut(ids[1], ids[2], 230);
+    ///
+    /// assert_eq!(old.edge_difference(&new), vec![(ids[0], ids[1])]);
+    /// assert_eq!(new.edge_difference(&old), vec![(ids[2], ids[0])]);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edge_difference(&self, other: &Self) -> Vec<(Id, Id)> {
+        self.edges()
+            .filter(|&(source, sink, _)| !other.has_edge(source, sink))
+            .map(|(source, sink, _)| (source, sink))
+            .collect()
+    }
+}
+
+impl<V, E: PartialEq> PGraph<V, E> {
+    /// Finds what changed between this PGraph and `other`, treating `self` as the older version and `other` as the
+    /// newer one.
+    ///
+    /// Since a PGraph and the PGraphs derived from it share [Id](struct.Id.html)s, vertices and edges are matched
+    /// up by Id. Comparing PGraphs that don't share history will report every vertex and edge as added or removed.
+    /// Changes to vertex data aren't reported.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut old = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = old.add_mut(1);
+    /// let id2 = old.add_mut(2);
+    /// old.connect_mut(id1, id2, 12);
+    /// old.connect_mut(id2, id1, 21);
+    ///
+    /// let mut new = old.clone();
+    /// let id3 = new.add_mut(3);
+    /// new.connect_mut(id3, id1, 31);
+    /// new.connect_mut(id1, id2, 120);
+    /// new.disconnect_mut(id2, id1);
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.added_vertices, vec![id3]);
+    /// assert!(diff.removed_vertices.is_empty());
+    /// assert_eq!(diff.added_edges, vec![(id3, id1)]);
+    /// assert_eq!(diff.removed_edges, vec![(id2, id1)]);
+    /// assert_eq!(diff.changed_edges, vec![(id1, id2)]);
+    ///
+    /// assert!(old.diff(&old).is_empty());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> GraphDiff {
+        let mut removed_edges = Vec::new();
+        let mut changed_edges = Vec::new();
+        for (source, sink, weight) in self.edges() {
+            match other.weight(source, sink) {
+                None => removed_edges.push((source, sink)),
+                Some(other_weight) if other_weight != weight => changed_edges.push((source, sink)),
+                Some(_) => (),
+            }
+        }
+
+        GraphDiff {
+            added_vertices: other.ids().filter(|id| !self.has_vertex(id)).collect(),
+            removed_vertices: self.ids().filter(|id| !other.has_vertex(id)).collect(),
+            added_edges: other
+                .edges()
+                .filter(|(source, sink, _)| !self.has_edge(source, sink))
+                .map(|(source, sink, _)| (source, sink))
+                .collect(),
+            removed_edges,
+            changed_edges,
+        }
+    }
+}
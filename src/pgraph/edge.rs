@@ -1,14 +1,29 @@
+use super::components::DisjointSets;
 use super::{Id, PGraph, Vertex};
 use std::borrow::Borrow;
-use std::ops::IndexMut;
-use std::sync::Arc;
 
 /// A view into a single edge on the graph. The edge may either exist and have a weight or not exist and have no weight.
 ///
-/// An `Edge` is constructed from the [`edge`](structs.PGraph.html#method.edge) method on [`PGraph`](structs.PGraph.html)
+/// An `Edge` is constructed from the [`edge`](struct.PGraph.html#method.edge) method on [`PGraph`](struct.PGraph.html)
+///
+/// If the PGraph was created by `dag_checked`, inserting a weight for a missing edge that would introduce a cycle panics.
 pub struct Edge<'a, V, E> {
     source: &'a mut Vertex<V, E>,
     sink: Id,
+    components: Option<&'a mut DisjointSets>,
+    closes_cycle: bool,
+    max_index: usize,
+}
+
+/// What happened to an edge's weight when it was set by [`upsert_edge_mut`](struct.PGraph.html#method.upsert_edge_mut).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EdgeUpsert<E> {
+    /// There was no edge, so one was created with the new weight.
+    Created,
+    /// There was already an edge with a weight equal to the new weight, so nothing changed.
+    Unchanged,
+    /// There was already an edge with a different weight, which was replaced. Holds the old weight.
+    Updated(E),
 }
 
 impl<'a, V: Clone, E> Edge<'a, V, E> {
@@ -23,12 +38,47 @@ impl<'a, V: Clone, E> Edge<'a, V, E> {
                 sink
             )
         } else {
+            // Indexing panics if `source` isn't in the graph.
+            let source = graph[source].id();
+            let closes_cycle = graph.closes_cycle(source, *sink);
+            let max_index = graph.clamp_index(usize::MAX);
+            let (source, components) = graph
+                .vertex_and_components_mut(source)
+                .expect("The source vertex was just found in the graph.");
             Self {
-                source: graph.index_mut(source),
+                source,
                 sink: *sink,
+                components,
+                closes_cycle,
+                max_index,
             }
         }
     }
+
+    /// Creates an Edge for the edge from `source` to `sink`, or returns `None` if either of them isn't in the graph, or if the graph
+    /// only allows acyclic edges and creating this one would close a cycle.
+    pub(crate) fn try_from<T: Borrow<Id>>(
+        graph: &'a mut PGraph<V, E>,
+        source: T,
+        sink: T,
+    ) -> Option<Self> {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+
+        if graph.has_vertex(sink) && !graph.closes_cycle(source, sink) {
+            let max_index = graph.clamp_index(usize::MAX);
+            graph
+                .vertex_and_components_mut(source)
+                .map(|(source, components)| Self {
+                    source,
+                    sink,
+                    components,
+                    closes_cycle: false,
+                    max_index,
+                })
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a, V, E> Edge<'a, V, E> {
@@ -72,10 +122,7 @@ impl<'a, V, E: Clone> Edge<'a, V, E> {
     /// # }
     /// ```
     pub fn or_insert(self, default: E) -> &'a mut E {
-        let sink = self.sink;
-        let edge = self.source.make_edge_mut(sink);
-        let (_, weight_arc) = edge.get_or_insert((sink, Arc::new(default)));
-        Arc::get_mut(weight_arc).unwrap()
+        self.or_insert_with(|| default)
     }
 
     /// Ensures this edge has a weight by inserting the result of the `default` function
@@ -112,10 +159,46 @@ impl<'a, V, E: Clone> Edge<'a, V, E> {
     /// # }
     /// ```
     pub fn or_insert_with<F: FnOnce() -> E>(self, default: F) -> &'a mut E {
-        let sink = self.sink;
-        let edge = self.source.make_edge_mut(sink);
-        let (_, weight_arc) = edge.get_or_insert_with(|| (sink, Arc::new(default())));
-        Arc::get_mut(weight_arc).unwrap()
+        if self.closes_cycle {
+            panic!(
+                "An edge from {:?} to {:?} would introduce a cycle.",
+                self.source.id(),
+                self.sink
+            )
+        }
+        if let Some(components) = self.components {
+            components.union(self.source.id().index(), self.sink.index());
+        }
+        self.source.weight_or_insert_with(self.sink, default)
+    }
+
+    /// Ensures this edge has a weight by inserting `default` if empty, like `or_insert`, and returns a mutable reference to the
+    /// edge's weight. If the edge has to be created, the source vertex's adjacency list is first pre-extended with empty slots for
+    /// edges to every vertex with an index up to and including `up_to_index` (see [`reserve_neighbors`](struct.PGraph.html#method.reserve_neighbors)),
+    /// so the later edges of a loop that connects the same source to many sinks don't have to extend it. Like `reserve_neighbors`,
+    /// `up_to_index` is clamped to the PGraph's highest slot index.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(0..100usize);
+    ///
+    /// let last = ids.len() - 1;
+    /// for (i, &sink) in ids.iter().enumerate() {
+    ///     *g.edge(ids[0], sink).or_insert_reserving(0, last) += i;
+    /// }
+    /// assert_eq!(g.outbound_ids(ids[0]).count(), 100);
+    /// assert_eq!(g[(ids[0], ids[last])], last);
+    /// # }
+    /// ```
+    pub fn or_insert_reserving(self, default: E, up_to_index: usize) -> &'a mut E {
+        if !self.source.is_connected(self.sink) {
+            self.source
+                .reserve_neighbors(up_to_index.min(self.max_index));
+        }
+        self.or_insert(default)
     }
 
     /// Provides in-place mutable access to an existing edge weight before any potential edge creation.
@@ -0,0 +1,189 @@
+use super::Id;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The reason an edge couldn't be created by [`connect_checked`](struct.PGraph.html#method.connect_checked).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConnectError {
+    /// The edge's source vertex isn't in the PGraph. (Reported even if the sink is missing too.)
+    SourceMissing(Id),
+    /// The edge's sink vertex isn't in the PGraph.
+    SinkMissing(Id),
+    /// The PGraph was created by `dag_checked`, and the edge (from the first Id to the second) would introduce a cycle.
+    WouldCycle(Id, Id),
+}
+
+impl Display for ConnectError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ConnectError::SourceMissing(id) => write!(
+                f,
+                "The source vertex with Id {:?} was not found in the graph.",
+                id
+            ),
+            ConnectError::SinkMissing(id) => write!(
+                f,
+                "The sink vertex with Id {:?} was not found in the graph.",
+                id
+            ),
+            ConnectError::WouldCycle(source, sink) => write!(
+                f,
+                "An edge from {:?} to {:?} would introduce a cycle.",
+                source, sink
+            ),
+        }
+    }
+}
+
+impl Error for ConnectError {}
+
+/// The error returned by [`add_at_mut`](struct.PGraph.html#method.add_at_mut) when the requested slot already holds a vertex.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OccupiedError {
+    /// The [Id](struct.Id.html) of the vertex already in the slot.
+    pub occupant: Id,
+}
+
+impl Display for OccupiedError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "The slot at index {} is already occupied by the vertex with Id {:?}.",
+            self.occupant.index(),
+            self.occupant
+        )
+    }
+}
+
+impl Error for OccupiedError {}
+
+/// The error returned by [`from_bytes`](struct.PGraph.html#method.from_bytes) when its input isn't a PGraph written by `to_bytes`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The input doesn't start with the header that `to_bytes` writes.
+    BadHeader,
+    /// The input ends partway through the PGraph.
+    UnexpectedEnd,
+    /// A count, length, or position in the input is too large to fit in a `usize`.
+    Overflow,
+    /// An edge ends at the vertex in position `sink`, but the input only has `vertices` vertices.
+    SinkOutOfRange { sink: usize, vertices: usize },
+    /// The input has `extra` bytes left over after the PGraph.
+    TrailingBytes { extra: usize },
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            DecodeError::BadHeader => write!(f, "The input doesn't start with a PGraph header."),
+            DecodeError::UnexpectedEnd => write!(f, "The input ended partway through the graph."),
+            DecodeError::Overflow => write!(f, "The input holds a number too large for a usize."),
+            DecodeError::SinkOutOfRange { sink, vertices } => write!(
+                f,
+                "An edge ends at vertex {}, but the graph only has {} vertices.",
+                sink, vertices
+            ),
+            DecodeError::TrailingBytes { extra } => {
+                write!(
+                    f,
+                    "The input has {} bytes left over after the graph.",
+                    extra
+                )
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// The error returned by algorithms that only work on directed acyclic graphs, like
+/// [`transitive_reduction`](struct.PGraph.html#method.transitive_reduction), when the PGraph has a cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CycleError {
+    /// The [Id](struct.Id.html) of a vertex on one of the PGraph's cycles.
+    pub vertex: Id,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "The graph has a cycle through the vertex with Id {:?}.",
+            self.vertex
+        )
+    }
+}
+
+impl Error for CycleError {}
+
+/// A broken internal invariant found by [`validate`](struct.PGraph.html#method.validate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationError {
+    /// The vertex in the slot at `index` has an [Id](struct.Id.html) with a different index.
+    MisplacedVertex { index: usize, id: Id },
+    /// The slot at `index` is empty, but isn't in the list of empty slots.
+    UnlistedEmptySlot(usize),
+    /// The slot at `index` is in the list of empty slots, but holds a vertex (or doesn't exist).
+    ListedNonEmptySlot(usize),
+    /// The edge from `source` to `sink` is stored in the slot for a different sink index (`slot`).
+    MisplacedEdge { source: Id, sink: Id, slot: usize },
+    /// The edge from `source` to `sink` ends at an index past the end of the PGraph.
+    EdgeOutOfBounds { source: Id, sink: Id },
+    /// The edge from `source` to `sink` ends at a vertex that isn't in the PGraph.
+    DanglingEdge { source: Id, sink: Id },
+    /// The vertex `id` has a cached edge count of `cached`, but actually has `actual` edges.
+    EdgeCountMismatch {
+        id: Id,
+        cached: usize,
+        actual: usize,
+    },
+    /// The edge from `source` to `sink` is missing from the PGraph's reverse index.
+    UnindexedEdge { source: Id, sink: Id },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ValidationError::MisplacedVertex { index, id } => write!(
+                f,
+                "The vertex with Id {:?} is stored in slot {}.",
+                id, index
+            ),
+            ValidationError::UnlistedEmptySlot(index) => {
+                write!(f, "Slot {} is empty but isn't listed as empty.", index)
+            }
+            ValidationError::ListedNonEmptySlot(index) => write!(
+                f,
+                "Slot {} is listed as empty but isn't an empty slot.",
+                index
+            ),
+            ValidationError::MisplacedEdge { source, sink, slot } => write!(
+                f,
+                "The edge from {:?} to {:?} is stored in slot {}.",
+                source, sink, slot
+            ),
+            ValidationError::EdgeOutOfBounds { source, sink } => write!(
+                f,
+                "The edge from {:?} to {:?} ends past the end of the graph.",
+                source, sink
+            ),
+            ValidationError::DanglingEdge { source, sink } => write!(
+                f,
+                "The edge from {:?} to {:?} ends at a vertex that isn't in the graph.",
+                source, sink
+            ),
+            ValidationError::EdgeCountMismatch { id, cached, actual } => write!(
+                f,
+                "The vertex with Id {:?} thinks it has {} edges, but it has {}.",
+                id, cached, actual
+            ),
+            ValidationError::UnindexedEdge { source, sink } => write!(
+                f,
+                "The edge from {:?} to {:?} is missing from the reverse index.",
+                source, sink
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
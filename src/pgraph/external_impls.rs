@@ -2,16 +2,63 @@
 
 use super::vertex::Vertex;
 use super::{Id, OutboundIter, PGraph, PredecessorIter};
+use petgraph::data::{DataMap, DataMapMut};
+use petgraph::graph::{IndexType, NodeIndex};
 use petgraph::visit::IntoNodeReferences;
 use petgraph::visit::{
-    Data, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors,
-    IntoNeighborsDirected, IntoNodeIdentifiers, NodeCompactIndexable, NodeCount, NodeIndexable,
-    Visitable,
+    Data, GetAdjacencyMatrix, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges,
+    IntoEdgesDirected, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers,
+    NodeCompactIndexable, NodeCount, NodeIndexable, Visitable,
 };
-use petgraph::{Directed, Direction};
-use std::collections::HashSet;
+use petgraph::{Directed, Direction, Graph};
+use std::collections::{HashMap, HashSet};
 use std::iter::Map;
 
+impl<V: Clone, E: Clone> PGraph<V, E> {
+    /// Copies all the nodes and edges of a `petgraph::Graph` into a new PGraph.
+    ///
+    /// Returns the new PGraph and a map from each of `g`'s node indices to the [Id](struct.Id.html) of the
+    /// vertex it became. Edge directions and weights are preserved. Since a PGraph can have at most one edge from
+    /// one vertex to another, if `g` has parallel edges, only the last inserted one is kept.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # use petgraph::Graph;
+    /// # fn main() {
+    /// let mut pet = Graph::<&str, usize>::new();
+    /// let a = pet.add_node("A");
+    /// let b = pet.add_node("B");
+    /// pet.add_edge(a, b, 1);
+    /// pet.add_edge(a, b, 2);
+    ///
+    /// let (g, ids) = PGraph::from_petgraph(&pet);
+    /// assert_eq!(g[(ids[&a],)], "A");
+    /// assert_eq!(g[(ids[&a], ids[&b])], 2);
+    /// assert!(!g.has_edge(ids[&b], ids[&a]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_petgraph<Ix: IndexType>(
+        g: &Graph<V, E, Directed, Ix>,
+    ) -> (Self, HashMap<NodeIndex<Ix>, Id>) {
+        let mut result = Self::new();
+        let ids: HashMap<_, _> = g
+            .node_indices()
+            .map(|index| (index, result.add_mut(g[index].clone())))
+            .collect();
+
+        for edge in g.raw_edges() {
+            result.connect_mut(
+                ids[&edge.source()],
+                ids[&edge.target()],
+                edge.weight.clone(),
+            );
+        }
+        (result, ids)
+    }
+}
+
 impl<V, E> GraphBase for PGraph<V, E> {
     type NodeId = Id;
     type EdgeId = (Id, Id);
@@ -26,6 +73,26 @@ impl<V, E> Data for PGraph<V, E> {
     type EdgeWeight = E;
 }
 
+impl<V, E> DataMap for PGraph<V, E> {
+    fn node_weight(&self, id: Id) -> Option<&V> {
+        self.vertex_data(id)
+    }
+
+    fn edge_weight(&self, (source, sink): (Id, Id)) -> Option<&E> {
+        self.weight(source, sink)
+    }
+}
+
+impl<V: Clone, E: Clone> DataMapMut for PGraph<V, E> {
+    fn node_weight_mut(&mut self, id: Id) -> Option<&mut V> {
+        self.vertex_data_mut(id)
+    }
+
+    fn edge_weight_mut(&mut self, (source, sink): (Id, Id)) -> Option<&mut E> {
+        self.weight_mut(source, sink)
+    }
+}
+
 impl<V, E> Visitable for PGraph<V, E> {
     type Map = HashSet<Id>;
 
@@ -108,10 +175,10 @@ impl<'a, V, E> IntoNodeReferences for &'a PGraph<V, E> {
 type NodeRefIter<'a, V, E> = Map<super::VertexIter<'a, V, E>, fn(&'a Vertex<V, E>) -> (Id, &'a V)>;
 
 impl<'a, V, E> IntoEdges for &'a PGraph<V, E> {
-    type Edges = std::iter::Chain<OutboundIter<'a, E>, PredecessorIter<'a, V, E>>;
+    type Edges = OutboundIter<'a, E>;
 
     fn edges(self, a: Id) -> Self::Edges {
-        self.outbound_edges(a).chain(self.predecessors(a))
+        self.outbound_edges(a)
     }
 }
 
@@ -150,10 +217,11 @@ impl<'a, V, E> IntoEdgesDirected for &'a PGraph<V, E> {
 
 impl<V, E> NodeCount for PGraph<V, E> {
     fn node_count(&self) -> usize {
-        self.empties.len()
+        self.count_vertices()
     }
 }
 
+/// Maps the live vertices onto the dense range `0..node_count()`, in slot order, by skipping over the empty slots.
 impl<V, E> NodeIndexable for PGraph<V, E> {
     fn node_bound(&self) -> usize {
         self.node_count()
@@ -161,19 +229,39 @@ impl<V, E> NodeIndexable for PGraph<V, E> {
 
     fn to_index(&self, a: Id) -> usize {
         let index = a.index();
-        index - self.empties.range(0..index).count()
+        index - self.empties.range(..index).count()
     }
 
-    fn from_index(&self, mut i: usize) -> Id {
-        for empty in self.empties.iter().cloned() {
-            if empty <= i {
-                i += 1;
+    fn from_index(&self, i: usize) -> Id {
+        // Every empty slot at or before the slot we're looking for pushes that slot one further from `i`.
+        let mut index = i;
+        for empty in self.empties.iter() {
+            if *empty <= index {
+                index += 1;
             } else {
                 break;
             }
         }
-        self.guts[i].as_ref().unwrap().id()
+        match &self.guts[index] {
+            Some(v) => v.id(),
+            None => unreachable!("Compact index {} landed on an empty slot.", i),
+        }
     }
 }
 
 impl<V, E> NodeCompactIndexable for PGraph<V, E> {}
+
+impl<V, E> GetAdjacencyMatrix for PGraph<V, E> {
+    /// The set of `(source, sink)` pairs that have an edge, using the indices from `NodeIndexable`.
+    type AdjMatrix = HashSet<(usize, usize)>;
+
+    fn adjacency_matrix(&self) -> Self::AdjMatrix {
+        self.edges()
+            .map(|(source, sink, _)| (self.to_index(source), self.to_index(sink)))
+            .collect()
+    }
+
+    fn is_adjacent(&self, matrix: &Self::AdjMatrix, a: Id, b: Id) -> bool {
+        matrix.contains(&(self.to_index(a), self.to_index(b)))
+    }
+}
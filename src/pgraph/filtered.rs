@@ -0,0 +1,236 @@
+//! A view of a `PGraph` that hides the vertices that don't pass a predicate, so `petgraph`'s algorithms can run on part of it.
+
+use super::{Id, PGraph};
+use petgraph::data::DataMap;
+use petgraph::visit::{
+    Data, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors,
+    IntoNeighborsDirected, IntoNodeIdentifiers, Visitable,
+};
+use petgraph::{Directed, Direction};
+use std::borrow::Borrow;
+use std::collections::HashSet;
+
+/// A read-only view of a [PGraph](struct.PGraph.html) that only contains the vertices for which a predicate returns `true`,
+/// and the edges between them. Created by [`filtered`](struct.PGraph.html#method.filtered).
+///
+/// The view doesn't copy anything; the predicate is called whenever a vertex is looked at, so it should be cheap and always
+/// give the same answer for the same vertex. Like `petgraph`'s `NodeFiltered`, the `petgraph` traits are implemented for
+/// references to the view.
+pub struct FilteredView<'a, V, E, F> {
+    graph: &'a PGraph<V, E>,
+    keep: F,
+}
+
+impl<V, E> PGraph<V, E> {
+    /// Creates a view of this PGraph that hides every vertex for which `keep` returns `false`, along with every edge
+    /// to or from those vertices. (See [FilteredView](struct.FilteredView.html).)
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate petgraph;
+    /// # use pgraph::PGraph;
+    /// # use petgraph::visit::Bfs;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id2, id3, 23);
+    ///
+    /// let odd = g.filtered(|_, data| data % 2 == 1);
+    /// assert_eq!(odd.ids().collect::<Vec<_>>(), vec![id1, id3]);
+    /// assert_eq!(odd.edges().count(), 1);
+    ///
+    /// let mut bfs = Bfs::new(&odd, id1);
+    /// let mut visited = vec![];
+    /// while let Some(id) = bfs.next(&odd) {
+    ///     visited.push(id);
+    /// }
+    /// assert_eq!(visited, vec![id1, id3]);
+    /// # }
+    /// ```
+    pub fn filtered<F: Fn(Id, &V) -> bool>(&self, keep: F) -> FilteredView<'_, V, E, F> {
+        FilteredView { graph: self, keep }
+    }
+}
+
+impl<'a, V, E, F: Fn(Id, &V) -> bool> FilteredView<'a, V, E, F> {
+    /// Returns the PGraph this view is of.
+    #[must_use]
+    pub fn graph(&self) -> &'a PGraph<V, E> {
+        self.graph
+    }
+
+    /// Returns `true` iff `id` is in the underlying PGraph and passes the view's predicate.
+    #[must_use]
+    pub fn has_vertex<T: Borrow<Id>>(&self, id: T) -> bool {
+        let id = *id.borrow();
+        matches!(self.graph.vertex_data(id), Some(data) if (self.keep)(id, data))
+    }
+
+    /// Returns an iterator over the [Id](struct.Id.html)s of the vertices in the view.
+    pub fn ids(&self) -> impl Iterator<Item = Id> + '_ {
+        self.graph.ids().filter(move |&id| self.has_vertex(id))
+    }
+
+    /// Returns an iterator over the edges in the view: the edges of the underlying PGraph whose source and sink are both in the view.
+    /// The iterator's items are (source: Id, sink: Id, edge: &E)
+    pub fn edges(&self) -> impl Iterator<Item = (Id, Id, &'a E)> + '_ {
+        let graph = self.graph;
+        graph
+            .edges()
+            .filter(move |&(source, sink, _)| self.has_vertex(source) && self.has_vertex(sink))
+    }
+}
+
+impl<'a, V, E, F> GraphBase for FilteredView<'a, V, E, F> {
+    type NodeId = Id;
+    type EdgeId = (Id, Id);
+}
+
+impl<'a, V, E, F> GraphProp for FilteredView<'a, V, E, F> {
+    type EdgeType = Directed;
+}
+
+impl<'a, V, E, F> Data for FilteredView<'a, V, E, F> {
+    type NodeWeight = V;
+    type EdgeWeight = E;
+}
+
+impl<'a, V, E, F: Fn(Id, &V) -> bool> DataMap for FilteredView<'a, V, E, F> {
+    fn node_weight(&self, id: Id) -> Option<&V> {
+        if self.has_vertex(id) {
+            self.graph.vertex_data(id)
+        } else {
+            None
+        }
+    }
+
+    fn edge_weight(&self, (source, sink): (Id, Id)) -> Option<&E> {
+        if self.has_vertex(source) && self.has_vertex(sink) {
+            self.graph.weight(source, sink)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, V, E, F> Visitable for FilteredView<'a, V, E, F> {
+    type Map = HashSet<Id>;
+
+    fn visit_map(&self) -> Self::Map {
+        HashSet::new()
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear()
+    }
+}
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool> IntoNodeIdentifiers for &'b FilteredView<'a, V, E, F> {
+    type NodeIdentifiers = FilteredIds<'a, 'b, V, E, F, super::IdIter<'a, V, E>>;
+
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        FilteredIds {
+            view: self,
+            ids: Some(self.graph.ids()),
+        }
+    }
+}
+
+type GraphNeighbors<'a, V, E> = <&'a PGraph<V, E> as IntoNeighborsDirected>::NeighborsDirected;
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool> IntoNeighbors for &'b FilteredView<'a, V, E, F> {
+    type Neighbors = FilteredIds<'a, 'b, V, E, F, GraphNeighbors<'a, V, E>>;
+
+    fn neighbors(self, a: Id) -> Self::Neighbors {
+        self.neighbors_directed(a, Direction::Outgoing)
+    }
+}
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool> IntoNeighborsDirected for &'b FilteredView<'a, V, E, F> {
+    type NeighborsDirected = FilteredIds<'a, 'b, V, E, F, GraphNeighbors<'a, V, E>>;
+
+    fn neighbors_directed(self, a: Id, d: Direction) -> Self::NeighborsDirected {
+        FilteredIds {
+            view: self,
+            ids: if self.has_vertex(a) {
+                Some(self.graph.neighbors_directed(a, d))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+type GraphEdges<'a, V, E> = <&'a PGraph<V, E> as IntoEdgesDirected>::EdgesDirected;
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool> IntoEdgeReferences for &'b FilteredView<'a, V, E, F> {
+    type EdgeRef = (Id, Id, &'a E);
+    type EdgeReferences = FilteredEdges<'a, 'b, V, E, F, super::EdgeIter<'a, V, E>>;
+
+    fn edge_references(self) -> Self::EdgeReferences {
+        FilteredEdges {
+            view: self,
+            edges: self.graph.edges(),
+        }
+    }
+}
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool> IntoEdges for &'b FilteredView<'a, V, E, F> {
+    type Edges = FilteredEdges<'a, 'b, V, E, F, GraphEdges<'a, V, E>>;
+
+    fn edges(self, a: Id) -> Self::Edges {
+        self.edges_directed(a, Direction::Outgoing)
+    }
+}
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool> IntoEdgesDirected for &'b FilteredView<'a, V, E, F> {
+    type EdgesDirected = FilteredEdges<'a, 'b, V, E, F, GraphEdges<'a, V, E>>;
+
+    fn edges_directed(self, a: Id, dir: Direction) -> Self::EdgesDirected {
+        FilteredEdges {
+            view: self,
+            edges: self.graph.edges_directed(a, dir),
+        }
+    }
+}
+
+/// Iterates over the [Id](struct.Id.html)s from an underlying iterator that are in a [FilteredView](struct.FilteredView.html).
+pub struct FilteredIds<'a, 'b, V, E, F, I> {
+    view: &'b FilteredView<'a, V, E, F>,
+    ids: Option<I>,
+}
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool, I: Iterator<Item = Id>> Iterator
+    for FilteredIds<'a, 'b, V, E, F, I>
+{
+    type Item = Id;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let view = self.view;
+        self.ids.as_mut()?.find(|&id| view.has_vertex(id))
+    }
+}
+
+/// Iterates over the edges from an underlying iterator whose source and sink are both in a [FilteredView](struct.FilteredView.html).
+pub struct FilteredEdges<'a, 'b, V, E, F, I> {
+    view: &'b FilteredView<'a, V, E, F>,
+    edges: I,
+}
+
+impl<'a, 'b, V, E, F: Fn(Id, &V) -> bool, I: Iterator<Item = (Id, Id, &'a E)>> Iterator
+    for FilteredEdges<'a, 'b, V, E, F, I>
+{
+    type Item = (Id, Id, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let view = self.view;
+        self.edges
+            .find(|&(source, sink, _)| view.has_vertex(source) && view.has_vertex(sink))
+    }
+}
@@ -1,21 +1,49 @@
-use crate::id::{Id, IdGen};
+use crate::id::{GenerationId, Id, IdGen};
 use im::{ordset::OrdSet, Vector};
+use petgraph::Direction;
 use std::borrow::{Borrow, Cow};
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
 use std::fmt::{Debug, Error, Formatter};
+use std::hash::{Hash, Hasher};
 use std::iter::{FilterMap, Flatten, FromIterator, IntoIterator, Map};
-use std::ops::{Index, IndexMut};
-
+use std::mem;
+use std::ops::{Add, Index, IndexMut};
+use std::sync::Arc;
+
+mod algo;
+mod bytes;
+mod components;
+mod diff;
 mod edge;
+mod error;
+mod filtered;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rand")]
+mod random;
+mod stats;
+mod undirected;
+mod validate;
 mod vertex;
 
-pub use self::edge::Edge;
-pub use self::vertex::{adj, Vertex};
+use self::components::{ComponentCache, DisjointSets};
+pub use self::diff::GraphDiff;
+pub use self::edge::{Edge, EdgeUpsert};
+pub use self::error::{ConnectError, CycleError, DecodeError, OccupiedError, ValidationError};
+pub use self::filtered::FilteredView;
+pub use self::stats::StructuralStats;
+pub use self::undirected::Undirected;
+pub use self::vertex::{adj, RemovedVertex, Vertex};
 
 // #[cfg(algorithms)]
 mod external_impls;
 
 type GraphInternal<V, E> = Vector<Option<Vertex<V, E>>>;
+type ReverseIndex = Vector<OrdSet<Id>>;
+type VertexParts<V> = Vec<Option<(Id, V)>>;
+type EdgeParts<E> = Vec<(Id, Id, E)>;
 
 /// Represents a persistent graph with data on each vertex (of type `V`) and directed, weighted edges.
 /// (Edge weights are of type `E`.) Uses [`Id`](struct.Id.html)s as references to vertices.
@@ -23,10 +51,31 @@ type GraphInternal<V, E> = Vector<Option<Vertex<V, E>>>;
 /// All of the `_mut` methods will mutate the PGraph in-place, while the corresponding methods without `_mut` will clone the existing PGraph and return a modified version.
 /// All of the `try_` methods do less redundant cloning and will not panic if their non-`try` counterparts would.
 /// All the graph data is held using structual sharing, so the cloning will be minimally expensive, with respect to both time and memory.
+///
+/// # Id validity
+///
+/// Removing a vertex frees its slot for reuse, but never its [`Id`](struct.Id.html). Every public method that removes
+/// vertices moves the PGraph to a new generation before it returns (methods that clone get a new generation from the clone),
+/// so a vertex added afterwards always gets an `Id` that differs from every `Id` removed before it, even if it lands in the same
+/// slot. Stale `Id`s therefore never alias new vertices; they are simply reported as missing. `remove_then_add_mut` bundles a
+/// removal and an addition for callers that want that guarantee spelled out at the call site.
+///
+/// PGraphs created by `new_no_generations` opt out of all of this, and their Ids can alias.
+///
+/// # Iteration order
+///
+/// Every iterator over the PGraph's vertices (`ids`, `iter_data`, and iterating over `&PGraph`) yields them in ascending order of
+/// their slot index, which is also ascending [`Id`](struct.Id.html) order. Iterators over edges (`edges`, `iter_weights`) go through the
+/// sources in that same order, and through each source's edges in ascending order of their sinks' indices. This order is stable: it
+/// only changes when the PGraph does. Note that `add_mut` fills the lowest empty slot first, so a vertex added after a removal may come
+/// before older vertices. `canonicalize` and `recreate` keep the vertices in the same relative order.
 pub struct PGraph<V, E> {
     guts: GraphInternal<V, E>,
     empties: OrdSet<usize>,
     idgen: IdGen,
+    reverse: Option<ReverseIndex>,
+    components: Option<ComponentCache>,
+    acyclic: bool,
 }
 
 // `derive(Clone)` only implements for <V: Clone, E: Clone> because of rust#26925
@@ -36,6 +85,9 @@ impl<V, E> Clone for PGraph<V, E> {
             guts: self.guts.clone(),
             empties: self.empties.clone(),
             idgen: self.idgen.clone(),
+            reverse: self.reverse.clone(),
+            components: self.components.clone(),
+            acyclic: self.acyclic,
         }
     }
 }
@@ -46,8 +98,58 @@ impl<V, E> Default for PGraph<V, E> {
     }
 }
 
+/// Builds a PGraph with one unconnected vertex for each item. The new vertices' [Id](struct.Id.html)s can be
+/// recovered with [`ids`](struct.PGraph.html#method.ids).
+/// # Examples
+///
+/// ```
+/// # use pgraph::PGraph;
+/// # fn main() {
+/// let g: PGraph<_, ()> = vec!["Roark", "Wynand"].into_iter().collect();
+///
+/// let data: Vec<_> = g.iter_data().cloned().collect();
+/// assert_eq!(data, vec!["Roark", "Wynand"]);
+/// assert_eq!(g.iter_weights().count(), 0);
+/// # }
+/// ```
+impl<V, E> FromIterator<V> for PGraph<V, E> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for data in iter {
+            result.add_mut(data);
+        }
+        result
+    }
+}
+
+/// The default form (`{:?}`) shows the PGraph's internals: its generation, every slot (including empty ones), and the full
+/// [Id](struct.Id.html)s of its vertices and edges.
+///
+/// The alternate form (`{:#?}`) is deterministic, so it's suitable for snapshot tests. It leaves out generations and empty slots,
+/// and refers to each vertex by its position among the live vertices (the index it would have after `canonicalize`). Each vertex
+/// gets a line with its position and data, followed by an indented line for each of its edges, with the sink's position and the
+/// weight. Data and weights use their default `Debug` form, so each vertex and edge stays on one line.
+/// # Examples
+///
+/// ```
+/// # use pgraph::PGraph;
+/// # fn main() {
+/// let mut g = PGraph::<&str, usize>::new();
+/// let ids: Vec<_> = g.add_all_mut(vec!["removed", "a", "b"]);
+/// g.connect_mut(ids[1], ids[2], 12);
+/// g.connect_mut(ids[1], ids[1], 11);
+/// g.remove_mut(ids[0]);
+///
+/// let expected = "PGraph {\n    0: \"a\"\n        -> 0: 11\n        -> 1: 12\n    1: \"b\"\n}";
+/// assert_eq!(format!("{:#?}", g), expected);
+/// # }
+/// ```
 impl<V: Debug, E: Debug> Debug for PGraph<V, E> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if f.alternate() {
+            return self.fmt_compact(f);
+        }
+
         write!(f, "PGraph ({:?}) {{", self.idgen)?;
         let mut any_vertices = false;
 
@@ -64,6 +166,76 @@ impl<V: Debug, E: Debug> Debug for PGraph<V, E> {
     }
 }
 
+/// Two PGraphs are equal if they have the same structure: the same number of live vertices, with equal data in the same
+/// iteration order, and equal edges between the corresponding vertices. A vertex is identified by its position among the
+/// live vertices rather than by its [Id](struct.Id.html), so generations, empty slots, and the reverse and component indices
+/// are ignored. A PGraph is therefore equal to the result of calling `canonicalize` or `recreate` on it.
+/// # Examples
+///
+/// ```
+/// # use pgraph::PGraph;
+/// # fn main() {
+/// let mut g1 = PGraph::<&str, usize>::new();
+/// let scratch = g1.add_mut("Toohey");
+/// let id1 = g1.add_mut("Roark");
+/// let id2 = g1.add_mut("Wynand");
+/// g1.connect_mut(id1, id2, 12);
+/// g1.remove_mut(scratch);
+///
+/// let mut g2 = PGraph::<&str, usize>::new();
+/// let id1 = g2.add_mut("Roark");
+/// let id2 = g2.add_mut("Wynand");
+/// g2.connect_mut(id1, id2, 12);
+///
+/// assert_eq!(g1, g2);
+///
+/// g2.connect_mut(id2, id1, 21);
+/// assert_ne!(g1, g2);
+/// # }
+/// ```
+impl<V: PartialEq, E: PartialEq> PartialEq for PGraph<V, E> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.count_vertices() != other.count_vertices() {
+            return false;
+        }
+
+        let (ranks, other_ranks) = (self.ranks(), other.ranks());
+        self.guts
+            .iter()
+            .flatten()
+            .zip(other.guts.iter().flatten())
+            .all(|(v1, v2)| {
+                v1.data() == v2.data()
+                    && v1.len_neighbors() == v2.len_neighbors()
+                    && v1.into_iter().zip(v2).all(|((sink1, e1), (sink2, e2))| {
+                        ranks[sink1.index()] == other_ranks[sink2.index()] && e1 == e2
+                    })
+            })
+    }
+}
+
+impl<V: Eq, E: Eq> Eq for PGraph<V, E> {}
+
+/// Hashes the structure of the PGraph, so that PGraphs that are equal (see the `PartialEq` impl) hash equally.
+///
+/// The canonical order is the iteration order: the number of live vertices, then each live vertex in ascending slot order,
+/// as its data, its out-degree, and each of its edges in ascending order of sink, as (position of the sink among the live
+/// vertices, weight). Ids, generations, and empty slots aren't hashed.
+impl<V: Hash, E: Hash> Hash for PGraph<V, E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let ranks = self.ranks();
+        self.count_vertices().hash(state);
+        for vertex in self.guts.iter().flatten() {
+            vertex.data().hash(state);
+            vertex.len_neighbors().hash(state);
+            for (sink, weight) in vertex {
+                ranks[sink.index()].hash(state);
+                weight.hash(state);
+            }
+        }
+    }
+}
+
 // helpers
 impl<V, E> PGraph<V, E> {
     /// Gets the current generation of the PGraph's IdGen
@@ -80,12 +252,228 @@ impl<V, E> PGraph<V, E> {
         self.empties.len()
     }
 
+    /// Counts the number of slots (empty or not) in the underlying vertex Vector
+    #[cfg(test)]
+    #[must_use]
+    pub fn count_slots(&self) -> usize {
+        self.guts.len()
+    }
+
+    /// Counts the number of live vertices in the PGraph
+    #[must_use]
+    fn count_vertices(&self) -> usize {
+        self.guts.len() - self.empties.len()
+    }
+
+    /// Writes the alternate form of the PGraph's `Debug` output, which identifies vertices by their position among the live vertices.
+    fn fmt_compact(&self, f: &mut Formatter) -> Result<(), Error>
+    where
+        V: Debug,
+        E: Debug,
+    {
+        let ranks = self.ranks();
+        write!(f, "PGraph {{")?;
+        for (rank, vertex) in self.into_iter().enumerate() {
+            write!(f, "\n    {}: {:?}", rank, vertex.data())?;
+            for (sink, weight) in vertex {
+                write!(f, "\n        -> {}: {:?}", ranks[sink.index()], weight)?;
+            }
+        }
+        write!(f, "\n}}")
+    }
+
+    /// Returns, for each slot, the position of the vertex in that slot among the live vertices. (Empty slots get the position
+    /// the next live vertex would.) Used to compare, hash, print, and encode PGraphs without looking at their slot layout.
+    #[must_use]
+    fn ranks(&self) -> Vec<usize> {
+        let mut rank = 0;
+        self.guts
+            .iter()
+            .map(|v_opt| {
+                let current = rank;
+                if v_opt.is_some() {
+                    rank += 1;
+                }
+                current
+            })
+            .collect()
+    }
+
     /// Finds an empty (`None`) slot in the underlying vector.
     /// Current implementation gets the slot with the first index
     #[must_use]
     fn find_empty(&self) -> Option<usize> {
         self.empties.get_min().cloned()
     }
+
+    /// Creates a new, empty PGraph that keeps a reverse index iff this one does, keeps track of its components iff this one does,
+    /// and rejects cycles iff this one does
+    #[must_use]
+    fn new_like(&self) -> Self {
+        let mut graph = Self::new();
+        graph.idgen = self.idgen.fresh();
+        graph.reverse = self.reverse.as_ref().map(|_| ReverseIndex::new());
+        graph.components = self
+            .components
+            .as_ref()
+            .map(|_| DisjointSets::default().into());
+        graph.acyclic = self.acyclic;
+        graph
+    }
+
+    /// Returns `true` iff this PGraph was created by `dag_checked` and creating an edge from `source` to `sink` would close a cycle,
+    /// i.e. there's no such edge yet, and `source` is `sink` or can be reached from it. Takes O(V + E) time in that mode, and O(1) time otherwise.
+    fn closes_cycle(&self, source: Id, sink: Id) -> bool {
+        self.acyclic && !self.has_edge(source, sink) && self.reaches_any(vec![sink], &[source])
+    }
+
+    /// Returns `true` iff this PGraph was created by `dag_checked` and contracting the edge from `source` to `sink` would leave a
+    /// cycle through the merged vertex, i.e. there's a path from `source` or `sink` back to one of them that doesn't just follow that edge.
+    fn contraction_closes_cycle(&self, source: Id, sink: Id) -> bool {
+        if !self.acyclic {
+            return false;
+        }
+
+        let starts = self
+            .outbound_ids(source)
+            .filter(|&next| next != sink)
+            .chain(self.outbound_ids(sink))
+            .collect();
+        self.reaches_any(starts, &[source, sink])
+    }
+
+    /// Returns `true` iff this PGraph was created by `dag_checked` and connecting each vertex in `path` to the next one would
+    /// create a cycle. That happens iff some vertex on the path is, or can already reach, a vertex that comes before it.
+    fn path_closes_cycle(&self, path: &[Id]) -> bool {
+        self.acyclic && (1..path.len()).any(|i| self.reaches_any(vec![path[i]], &path[..i]))
+    }
+
+    /// Returns `true` iff any of `targets` is one of `starts`, or can be reached from one of them by following outbound edges.
+    fn reaches_any(&self, starts: Vec<Id>, targets: &[Id]) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = starts;
+        while let Some(id) = stack.pop() {
+            if targets.contains(&id) {
+                return true;
+            }
+            if seen.insert(id) {
+                stack.extend(self.outbound_edges(id).map(|(_, next, _)| next));
+            }
+        }
+        false
+    }
+
+    /// Creates an edge from `source` to `sink`, both of which have to be in the PGraph, without checking whether it closes a cycle.
+    ///
+    /// Used by `connect_mut` and friends once they've done their checks, and to copy edges that already exist in a PGraph with the
+    /// same structure, which can't introduce a cycle that wasn't already there.
+    fn connect_unchecked_mut(&mut self, source: Id, sink: Id, weight: E) {
+        if let Some((vertex, _)) = self.vertex_and_components_mut(source) {
+            vertex.connect_to(sink, weight);
+        }
+        self.index_edge(source, sink);
+        self.join_components(source, sink);
+    }
+
+    /// Records `source` as a possible predecessor of `sink` in the reverse index, if this PGraph keeps one.
+    ///
+    /// The reverse index only has to hold a superset of each vertex's predecessors, since every entry is checked
+    /// against the actual edges when it's read. So, it's fine to record an edge that doesn't end up being created.
+    fn index_edge(&mut self, source: Id, sink: Id) {
+        if let Some(reverse) = &mut self.reverse {
+            while reverse.len() <= sink.index() {
+                reverse.push_back(OrdSet::new());
+            }
+            if let Some(sources) = reverse.get_mut(sink.index()) {
+                sources.insert(source);
+            }
+        }
+    }
+
+    /// Removes `source` from the possible predecessors of `sink` in the reverse index, if this PGraph keeps one.
+    fn unindex_edge(&mut self, source: Id, sink: Id) {
+        if let Some(Some(sources)) = self.reverse.as_mut().map(|r| r.get_mut(sink.index())) {
+            sources.remove(&source);
+        }
+    }
+
+    /// Forgets all of the possible predecessors recorded for the slot at `index`, if this PGraph keeps a reverse index.
+    fn unindex_slot(&mut self, index: usize) {
+        if let Some(Some(sources)) = self.reverse.as_mut().map(|r| r.get_mut(index)) {
+            *sources = OrdSet::new();
+        }
+    }
+
+    /// Gets the vertex with [Id](struct.Id.html) `id` along with the PGraph's components (if it keeps track of them),
+    /// so that both can be borrowed mutably at once.
+    fn vertex_and_components_mut(
+        &mut self,
+        id: Id,
+    ) -> Option<(&mut Vertex<V, E>, Option<&mut DisjointSets>)> {
+        let components = self.components.as_mut().and_then(ComponentCache::get_mut);
+        match self.guts.get_mut(id.index()) {
+            Some(Some(vertex)) if vertex.same_id(id) => Some((vertex, components)),
+            _ => None,
+        }
+    }
+
+    /// Gets the vertex with [Id](struct.Id.html) `id` mutably, for changes that can't remove any of its edges, so the PGraph's
+    /// components stay valid. (`vertex_mut` and `IndexMut` can't assume that.) Panics if `id` isn't in the PGraph.
+    fn vertex_keeping_components_mut(&mut self, id: Id) -> &mut Vertex<V, E> {
+        if !self.has_vertex(id) {
+            self.missing_vertex(id)
+        }
+        self.vertex_and_components_mut(id).unwrap().0
+    }
+
+    /// Clamps `up_to_index` to the highest slot index in the PGraph, so reserving adjacency slots can't go past every vertex.
+    fn clamp_index(&self, up_to_index: usize) -> usize {
+        up_to_index.min(self.guts.len().saturating_sub(1))
+    }
+
+    /// Removes a vertex without incrementing the PGraph's generation. Returns the removed vertex.
+    ///
+    /// Not public because while it isn't `unsafe`, calling it wouthout calling
+    /// `self.idgen.next_gen()` afterwards potentially causes use-after-free problems.
+    /// (If the removed vertex was in the most recent generation, then adding a new
+    /// vertex in its slot will have the same [Id](struct.Id.html) as the old vertex.)
+    fn remove_mut_no_inc<T: Borrow<Id>>(&mut self, id: T) -> Option<Vertex<V, E>> {
+        let id = *id.borrow();
+        let index = id.index();
+        if self.has_reverse_index() {
+            let sinks: Vec<Id> = self[id].neighbor_ids().collect();
+            for sink in sinks {
+                self.unindex_edge(id, sink);
+            }
+        }
+        let removed = self.guts.get_mut(index).and_then(Option::take);
+        self.empties.insert(index);
+        self.disconnect_all_inc_mut(id);
+        self.unindex_slot(index);
+        self.invalidate_components();
+        removed
+    }
+
+    /// Panics with a message explaining why `id` doesn't point to a vertex in this PGraph.
+    fn missing_vertex(&self, id: Id) -> ! {
+        match self.guts.get(id.index()) {
+            Some(Some(_)) => panic!("The Id {:?} is of an invalid generation. It does not correspond to any vertices in this graph.", id),
+            Some(None) => panic!("No vertex found for Id {:?}. It has likely been removed from the graph.", id),
+            None => panic!("No vertex found for Id {:?}. The Id either comes from a chlid or another graph family.", id),
+        }
+    }
+
+    /// Disconnects all the edges that end at `sink`, returning how many there were.
+    fn disconnect_all_inc_mut(&mut self, sink: Id) -> usize {
+        let sources: Vec<Id> = self.predecessor_ids(sink).collect();
+        for &source in &sources {
+            if let Some((vertex, _)) = self.vertex_and_components_mut(source) {
+                vertex.disconnect(sink);
+            }
+            self.unindex_edge(source, sink);
+        }
+        sources.len()
+    }
 }
 
 impl<V, E> PGraph<V, E> {
@@ -96,9 +484,164 @@ impl<V, E> PGraph<V, E> {
             guts: GraphInternal::new(),
             empties: OrdSet::new(),
             idgen: IdGen::new(),
+            reverse: None,
+            components: None,
+            acyclic: false,
+        }
+    }
+
+    /// Creates a new, empty PGraph that keeps a reverse index of each vertex's incoming edges.
+    ///
+    /// By default, finding the edges that end at a vertex means scanning every vertex in the PGraph. With a reverse index,
+    /// `predecessors`, `predecessor_ids`, and `in_degree` only have to look at the vertices that have (or recently had) an edge
+    /// to that vertex, so they take O(in-degree) time instead of O(V). The cost is an extra set per vertex holding an entry for each
+    /// of its incoming edges, which roughly doubles the memory used by the edges, plus the time to keep it up to date as edges are
+    /// added and removed.
+    ///
+    /// The index is opt-in, and is carried over when the PGraph is cloned, modified, or canonicalized.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::with_reverse_index();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    ///
+    /// assert!(g.has_reverse_index());
+    /// assert_eq!(g.in_degree(ids[2]), 2);
+    ///
+    /// g.remove_mut(ids[0]);
+    /// assert_eq!(g.predecessor_ids(ids[2]).collect::<Vec<_>>(), vec![ids[1]]);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_reverse_index() -> Self {
+        Self {
+            reverse: Some(ReverseIndex::new()),
+            components: None,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a new, empty PGraph that doesn't protect its [Id](struct.Id.html)s with generations. This is meant for throwaway PGraphs
+    /// whose whole lifecycle is under the caller's control, where checking Ids for use-after-free isn't worth the (small) cost.
+    ///
+    /// **Ids can alias in this mode.** The PGraph never moves to a new generation, and its clones share its generation, so
+    /// an Id is effectively just a slot index: after a vertex is removed, its old Id refers to whatever vertex is added to its slot
+    /// next, and Ids from a clone refer to the same slots in the original. (See [Id validity](struct.PGraph.html#id-validity).)
+    /// Ids still don't work on PGraphs that weren't cloned from this one. PGraphs derived from this one with `canonicalize`,
+    /// `recreate`, and the like are in this mode, too.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, usize>::new_no_generations();
+    /// assert!(!g.has_generations());
+    ///
+    /// let old = g.add_mut("old");
+    /// g.remove_mut(old);
+    /// let new = g.add_mut("new");
+    /// assert_eq!(old, new);
+    /// assert_eq!(g[(old,)], "new");
+    ///
+    /// let h = g.clone();
+    /// assert_eq!(h.current_generation(), g.current_generation());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new_no_generations() -> Self {
+        Self {
+            idgen: IdGen::new_fixed(),
+            ..Self::new()
+        }
+    }
+
+    /// Returns `true` iff this PGraph protects its [Id](struct.Id.html)s with generations, i.e. it wasn't created by
+    /// `new_no_generations` (or derived from a PGraph that was).
+    #[must_use]
+    pub fn has_generations(&self) -> bool {
+        !self.idgen.is_fixed()
+    }
+
+    /// Creates a new, empty PGraph that stays a directed acyclic graph by construction: every method that creates edges refuses to
+    /// create one that would introduce a cycle (including self-loops). The methods that panic when a vertex is missing, like `connect_mut`
+    /// and inserting through `edge`, panic. The ones that report success, like `try_connect_mut`, `insert_edge_mut`, and
+    /// `connect_path_mut`, return `false` (`connect_path_mut` checks the whole path before creating any of it); `try_edge` and
+    /// `weight_or_default_mut` return `None`; and `connect_checked` returns `ConnectError::WouldCycle`. `contract_edge_mut` refuses
+    /// contractions that would close a cycle.
+    ///
+    /// Before each new edge is created, the PGraph searches for a path from `sink` back to `source`, so every connect costs O(V + E)
+    /// time. (Overwriting an existing edge's weight doesn't need the search.) The check is opt-in, so PGraphs created any other way
+    /// aren't slowed down. Like the other modes, it's carried over when the PGraph is cloned, modified, or canonicalized, but not
+    /// to its `complement`, which is almost never acyclic.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::{ConnectError, PGraph};
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, ()>::dag_checked();
+    /// assert!(g.is_dag_checked());
+    ///
+    /// let build = g.add_mut("build");
+    /// let test = g.add_mut("test");
+    /// let deploy = g.add_mut("deploy");
+    ///
+    /// assert!(g.try_connect_mut(deploy, test, ()));
+    /// assert!(g.try_connect_mut(test, build, ()));
+    ///
+    /// assert!(!g.try_connect_mut(build, deploy, ()));
+    /// assert_eq!(
+    ///     g.connect_checked(build, build, ()),
+    ///     Err(ConnectError::WouldCycle(build, build))
+    /// );
+    /// assert!(!g.has_edge(build, deploy));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn dag_checked() -> Self {
+        Self {
+            acyclic: true,
+            ..Self::new()
         }
     }
 
+    /// Returns `true` iff this PGraph refuses to create edges that would introduce a cycle. (See `dag_checked`.)
+    #[must_use]
+    pub fn is_dag_checked(&self) -> bool {
+        self.acyclic
+    }
+
+    /// Returns `true` iff this PGraph keeps a reverse index of incoming edges. (See `with_reverse_index`.)
+    #[must_use]
+    pub fn has_reverse_index(&self) -> bool {
+        self.reverse.is_some()
+    }
+
+    /// Gets the PGraph's current generation. Vertices added to the PGraph get [Id](struct.Id.html)s from this generation.
+    ///
+    /// The generation changes whenever the PGraph is cloned or has vertices removed (unless it was created by `new_no_generations`),
+    /// and can be nondeterministic, so it's only meaningful when compared for equality with another generation.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id = g.add_mut(1);
+    /// assert_eq!(g.current_generation(), id.generation_id());
+    ///
+    /// let h = g.clone();
+    /// assert_ne!(g.current_generation(), h.current_generation());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn current_generation(&self) -> GenerationId {
+        self.idgen.generation_id()
+    }
+
     /// Checks if the given Id points to a valid [Vertex](struct.Vertex.html). Equivalent to `self.vertex(id).is_some()`.
     /// # Examples
     ///
@@ -116,6 +659,47 @@ impl<V, E> PGraph<V, E> {
         self.vertex(id).is_some()
     }
 
+    /// Returns `true` iff every Id in `ids` points to a [Vertex](struct.Vertex.html) in the PGraph, stopping at the first one that doesn't.
+    /// (Returns `true` if `ids` is empty.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// assert!(g.all_present(&ids));
+    ///
+    /// g.remove_mut(ids[1]);
+    /// assert!(!g.all_present(&ids));
+    /// assert!(g.all_present(vec![ids[0], ids[2]]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn all_present<T: Borrow<Id>, I: IntoIterator<Item = T>>(&self, ids: I) -> bool {
+        ids.into_iter().all(|id| self.has_vertex(id))
+    }
+
+    /// Returns `true` iff at least one Id in `ids` points to a [Vertex](struct.Vertex.html) in the PGraph, stopping at the first one that does.
+    /// (Returns `false` if `ids` is empty.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.remove_all_mut(&ids[1..]);
+    /// assert!(g.any_present(&ids));
+    /// assert!(!g.any_present(&ids[1..]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn any_present<T: Borrow<Id>, I: IntoIterator<Item = T>>(&self, ids: I) -> bool {
+        ids.into_iter().any(|id| self.has_vertex(id))
+    }
+
     /// Gets the [Vertex](struct.Vertex.html) corresponding to a given [Id](struct.Id.html). Will return `None` if one cannot be found.
     ///
     /// Some reasons this could occur are:
@@ -168,66 +752,251 @@ impl<V, E> PGraph<V, E> {
         self.vertex(id).map(|v| v.data())
     }
 
-    /// Returns true iff there exist vertices corresponding to both `source` and `sink` and `source` has an outgoing edge to `sink`.
+    /// Gets the data from the [Vertices](struct.Vertex.html) corresponding to each of the given [Id](struct.Id.html)s, in the same order as `ids`.
+    /// Each element is `None` if the corresponding [Vertex](struct.Vertex.html) cannot be found.
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// let g = PGraph::<usize, usize>::new();
-    ///
-    /// let (g, id1) = g.add(1);
-    /// let (g, id2) = g.add(2);
-    /// let (g, id3) = g.add(3);
-    ///
-    /// let g = g.connect(id1, id2, 12);
-    /// let g = g.connect(id3, id2, 32);
-    /// let g = g.connect(id1, id3, 13);
-    ///
-    /// assert!(g.has_edge(id1, id2));
-    /// assert!(g.has_edge(id1, id3));
-    /// assert!(g.has_edge(id3, id2));
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<&str, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec!["a", "b", "c"]);
     ///
-    /// assert!(!g.has_edge(id2, id1));
-    /// assert!(!g.has_edge(id3, id1));
-    /// assert!(!g.has_edge(id2, id3));
+    /// let path = vec![ids[2], bad_id, ids[0]];
+    /// assert_eq!(g.vertex_data_all(&path), vec![Some(&"c"), None, Some(&"a")]);
     /// # }
     /// ```
     #[must_use]
-    pub fn has_edge<T: Borrow<Id>>(&self, source: T, sink: T) -> bool {
-        self.vertex(source).map_or(false, |v| v.is_connected(sink))
+    pub fn vertex_data_all<T: Borrow<Id>, I: IntoIterator<Item = T>>(
+        &self,
+        ids: I,
+    ) -> Vec<Option<&V>> {
+        ids.into_iter().map(|id| self.vertex_data(id)).collect()
     }
 
-    /// If there exists an outgoing edge from `source` to `sink`, returns a reference to that edge's weight. Otherwise, returns `None`.
+    /// Creates a new PGraph with the same vertices and edges as this one, where each vertex's data is the result of calling
+    /// `vertex_map` with the vertex's [Id](struct.Id.html) and data, and each edge's weight is the result of calling `edge_map` with
+    /// the edge's source, sink, and weight. Every vertex and edge is visited once, and neither `V` nor `E` has to be `Clone`.
+    ///
+    /// The new PGraph has the same layout as this one, so Ids from this PGraph work on it (and vice versa).
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// let g = PGraph::<usize, usize>::new();
-    ///
-    /// let (g, id1) = g.add(1);
-    /// let (g, id2) = g.add(2);
-    /// let (g, id3) = g.add(3);
-    ///
-    /// let g = g.connect(id1, id2, 12);
-    /// let g = g.connect(id3, id2, 32);
-    /// let g = g.connect(id1, id3, 13);
+    /// let mut g = PGraph::<&str, usize>::new();
     ///
-    /// assert_eq!(*g.weight(id1, id2).unwrap(), 12);
-    /// assert_eq!(*g.weight(id1, id3).unwrap(), 13);
-    /// assert_eq!(*g.weight(id3, id2).unwrap(), 32);
+    /// let id1 = g.add_mut("Phoenix");
+    /// let id2 = g.add_mut("Durango");
+    /// g.connect_mut(id1, id2, 1632);
     ///
-    /// assert!(g.weight(id2, id1).is_none());
-    /// assert!(g.weight(id3, id1).is_none());
-    /// assert!(g.weight(id2, id3).is_none());
+    /// let m = g.map(|_, name| name.len(), |_, _, km| *km as f64 * 0.62);
+    /// assert_eq!(m[(id1,)], 7);
+    /// assert_eq!(m[(id1, id2)], 1011.84);
+    /// assert!(!m.has_edge(id2, id1));
     /// # }
     /// ```
     #[must_use]
-    pub fn weight<T: Borrow<Id>>(&self, source: T, sink: T) -> Option<&E> {
+    pub fn map<W, G, FN, GN>(&self, mut vertex_map: FN, mut edge_map: GN) -> PGraph<W, G>
+    where
+        FN: FnMut(Id, &V) -> W,
+        GN: FnMut(Id, Id, &E) -> G,
+    {
+        let guts = self
+            .guts
+            .iter()
+            .map(|slot| {
+                slot.as_ref().map(|v| {
+                    let source = v.id();
+                    v.map(vertex_map(source, v.data()), |sink, weight| {
+                        edge_map(source, sink, weight)
+                    })
+                })
+            })
+            .collect();
+
+        PGraph {
+            guts,
+            empties: self.empties.clone(),
+            idgen: self.idgen.clone(),
+            reverse: self.reverse.clone(),
+            components: self.components.clone(),
+            acyclic: self.acyclic,
+        }
+    }
+
+    /// Removes the vertex `id` (and all edges from and to it) from the PGraph, in-place, and returns its data without cloning it.
+    ///
+    /// Returns `None` if `id` isn't in the PGraph, or if the vertex's data is shared with another PGraph (e.g. a clone this one
+    /// was made from). In the second case nothing is removed, so `has_vertex` tells the two cases apart.
+    /// This is meant for reclaiming the data of a PGraph that's about to be discarded.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<String, usize>::new();
+    /// let id1 = g.add_mut("one".to_string());
+    /// let id2 = g.add_mut("two".to_string());
+    ///
+    /// let mut h = g.clone();
+    /// assert_eq!(h.take_vertex_data(id1), None);
+    /// assert!(h.has_vertex(id1));
+    ///
+    /// drop(h);
+    /// assert_eq!(g.take_vertex_data(id2), Some("two".to_string()));
+    /// assert_eq!(g.take_vertex_data(id2), None);
+    /// # }
+    /// ```
+    pub fn take_vertex_data<T: Borrow<Id>>(&mut self, id: T) -> Option<V> {
+        let id = *id.borrow();
+        // Getting the vertex mutably unshares it from any clones, so that a clone's copy of it counts as a handle to the data.
+        if !self.vertex_and_components_mut(id)?.0.has_unique_data() {
+            return None;
+        }
+
+        let removed = self.remove_mut_no_inc(id);
+        self.idgen.next_gen();
+        removed.and_then(|vertex| vertex.into_data().ok())
+    }
+
+    /// Returns true iff there exist vertices corresponding to both `source` and `sink` and `source` has an outgoing edge to `sink`.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let g = PGraph::<usize, usize>::new();
+    ///
+    /// let (g, id1) = g.add(1);
+    /// let (g, id2) = g.add(2);
+    /// let (g, id3) = g.add(3);
+    ///
+    /// let g = g.connect(id1, id2, 12);
+    /// let g = g.connect(id3, id2, 32);
+    /// let g = g.connect(id1, id3, 13);
+    ///
+    /// assert!(g.has_edge(id1, id2));
+    /// assert!(g.has_edge(id1, id3));
+    /// assert!(g.has_edge(id3, id2));
+    ///
+    /// assert!(!g.has_edge(id2, id1));
+    /// assert!(!g.has_edge(id3, id1));
+    /// assert!(!g.has_edge(id2, id3));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn has_edge<T: Borrow<Id>>(&self, source: T, sink: T) -> bool {
+        self.vertex(source).map_or(false, |v| v.is_connected(sink))
+    }
+
+    /// If there exists an outgoing edge from `source` to `sink`, returns a reference to that edge's weight. Otherwise, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let g = PGraph::<usize, usize>::new();
+    ///
+    /// let (g, id1) = g.add(1);
+    /// let (g, id2) = g.add(2);
+    /// let (g, id3) = g.add(3);
+    ///
+    /// let g = g.connect(id1, id2, 12);
+    /// let g = g.connect(id3, id2, 32);
+    /// let g = g.connect(id1, id3, 13);
+    ///
+    /// assert_eq!(*g.weight(id1, id2).unwrap(), 12);
+    /// assert_eq!(*g.weight(id1, id3).unwrap(), 13);
+    /// assert_eq!(*g.weight(id3, id2).unwrap(), 32);
+    ///
+    /// assert!(g.weight(id2, id1).is_none());
+    /// assert!(g.weight(id3, id1).is_none());
+    /// assert!(g.weight(id2, id3).is_none());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn weight<T: Borrow<Id>>(&self, source: T, sink: T) -> Option<&E> {
         self.vertex(source).and_then(|v| v.weight(sink))
     }
 
+    /// Gets the [Vertex](struct.Vertex.html) that `g[id]` would, or `None` where `g[id]` would panic. The same lookup as `vertex`,
+    /// named to pair with the `Index` impls.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.remove_mut(id2);
+    ///
+    /// assert_eq!(g.get_vertex(id1).map(|v| *v.data()), Some(1));
+    /// assert!(g.get_vertex(id2).is_none());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get_vertex<T: Borrow<Id>>(&self, id: T) -> Option<&Vertex<V, E>> {
+        self.vertex(id)
+    }
+
+    /// Gets the weight that `g[(source, sink)]` would, or `None` where `g[(source, sink)]` would panic (because `source` isn't in
+    /// the PGraph, or has no edge to `sink`). The same lookup as `weight`, named to pair with the `Index` impls.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 12);
+    ///
+    /// assert_eq!(g.get_edge_indexed(id1, id2), Some(&12));
+    /// assert_eq!(g.get_edge_indexed(id2, id1), None);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get_edge_indexed<T: Borrow<Id>>(&self, source: T, sink: T) -> Option<&E> {
+        self.weight(source, sink)
+    }
+
+    /// Returns the weights of the edges between `a` and `b` in both directions, as `(a -> b, b -> a)`.
+    /// Each weight is `None` if there's no edge in that direction.
+    ///
+    /// If `a == b`, both positions hold the weight of that vertex's self-loop.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 21);
+    /// g.connect_mut(id2, id3, 23);
+    /// g.connect_mut(id3, id3, 33);
+    ///
+    /// assert_eq!(g.edges_between(id1, id2), (Some(&12), Some(&21)));
+    /// assert_eq!(g.edges_between(id3, id2), (None, Some(&23)));
+    /// assert_eq!(g.edges_between(id1, id3), (None, None));
+    /// assert_eq!(g.edges_between(id3, id3), (Some(&33), Some(&33)));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edges_between<T: Borrow<Id>>(&self, a: T, b: T) -> (Option<&E>, Option<&E>) {
+        let (a, b) = (a.borrow(), b.borrow());
+        match (self.vertex(a), self.vertex(b)) {
+            (Some(a_vertex), Some(b_vertex)) => (a_vertex.weight(b), b_vertex.weight(a)),
+            _ => (None, None),
+        }
+    }
+
     /// Modifies the PGraph to contain a new vertex containing `data`. (The vertex won't be connected to anything.)
     ///
     /// Returns the new PGraph and the new vertex's Id.
@@ -267,11 +1036,15 @@ impl<V, E> PGraph<V, E> {
     pub fn add_mut(&mut self, data: V) -> Id {
         match self.find_empty() {
             Some(index) => {
+                self.empties.remove(&index);
+                self.unindex_slot(index);
+                self.track_component(index);
                 let id = self.idgen.create_id(index);
                 self.guts.set(index, Some(Vertex::from(id, data)));
                 id
             }
             None => {
+                self.track_component(self.guts.len());
                 let id = self.idgen.create_id(self.guts.len());
                 self.guts.push_back(Some(Vertex::from(id, data)));
                 id
@@ -279,6 +1052,47 @@ impl<V, E> PGraph<V, E> {
         }
     }
 
+    /// Modifies the PGraph in-place to contain a new vertex containing `data`, placed in the slot at `index`. (The vertex won't be connected to anything.)
+    /// If `index` is past the end of the PGraph, the PGraph grows to fit it, leaving the slots in between empty.
+    ///
+    /// Useful for rebuilding a PGraph with the same layout as another one. Only the vertex's index is controlled;
+    /// its [Id](struct.Id.html) still comes from this PGraph's current generation.
+    ///
+    /// Returns the new vertex's Id, or an [OccupiedError](struct.OccupiedError.html) if there is already a vertex in that slot.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id3 = g.add_at_mut(3, 3).unwrap();
+    /// let id0 = g.add_mut(0);
+    ///
+    /// assert_eq!(g[(id3,)], 3);
+    /// assert_eq!(g[(id0,)], 0);
+    /// assert_eq!(g.add_at_mut(3, 4).unwrap_err().occupant, id3);
+    /// assert_eq!(g.ids().collect::<Vec<_>>(), vec![id0, id3]);
+    /// # }
+    /// ```
+    pub fn add_at_mut(&mut self, index: usize, data: V) -> Result<Id, OccupiedError> {
+        if let Some(Some(vertex)) = self.guts.get(index) {
+            return Err(OccupiedError {
+                occupant: vertex.id(),
+            });
+        }
+
+        while self.guts.len() <= index {
+            self.empties.insert(self.guts.len());
+            self.guts.push_back(None);
+        }
+        self.empties.remove(&index);
+        self.unindex_slot(index);
+        self.track_component(index);
+        let id = self.idgen.create_id(index);
+        self.guts.set(index, Some(Vertex::from(id, data)));
+        Ok(id)
+    }
+
     /// Adds multiple vertices to the PGraph. Each contains one of the elements contained in `data_iter`.  
     /// Returns the new PGraph and a Vec of the added [Id](struct.Id.html)s. The order of [Id](struct.Id.html)s in the Vec correspond the position in the `data_iter` from which that vertex's data came.
     ///
@@ -335,7 +1149,8 @@ impl<V, E> PGraph<V, E> {
             .collect()
     }
 
-    /// Returns an iterator over all the valid vertex [Id](struct.Id.html)s in the PGraph
+    /// Returns an iterator over all the valid vertex [Id](struct.Id.html)s in the PGraph, in ascending order.
+    /// (See [Iteration order](struct.PGraph.html#iteration-order).)
     /// # Examples
     ///
     /// ```
@@ -366,7 +1181,8 @@ impl<V, E> PGraph<V, E> {
         })
     }
 
-    /// Returns an iterator over all the valid vertex data in the PGraph
+    /// Returns an iterator over all the valid vertex data in the PGraph, in ascending order of the vertices' [Id](struct.Id.html)s.
+    /// (See [Iteration order](struct.PGraph.html#iteration-order).)
     /// # Examples
     ///
     /// ```
@@ -394,6 +1210,34 @@ impl<V, E> PGraph<V, E> {
         })
     }
 
+    /// Builds a map from the result of calling `key` on each vertex's data to that vertex's [Id](struct.Id.html), in one pass over the PGraph.
+    ///
+    /// If several vertices have the same key, the last one wins: the key maps to whichever of them comes last in
+    /// [iteration order](struct.PGraph.html#iteration-order).
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, ()>::new();
+    ///
+    /// let roark = g.add_mut("Roark");
+    /// let _ = g.add_mut("Francon");
+    /// let mallory = g.add_mut("Mallory");
+    ///
+    /// let by_initial = g.index_by(|name| name.chars().next());
+    /// assert_eq!(by_initial[&Some('R')], roark);
+    /// assert_eq!(by_initial[&Some('M')], mallory);
+    /// assert_eq!(by_initial.len(), 3);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn index_by<K: Eq + Hash, F: FnMut(&V) -> K>(&self, mut key: F) -> HashMap<K, Id> {
+        self.into_iter()
+            .map(|vertex| (key(vertex.data()), vertex.id()))
+            .collect()
+    }
+
     /// Returns an iterator over all wieghts of edges existing in the PGraph
     /// # Examples
     ///
@@ -432,24 +1276,125 @@ impl<V, E> PGraph<V, E> {
     /// The iterator's items (source: Id, sink: Id, edge: &E)
     #[must_use]
     pub fn predecessors<T: Borrow<Id>>(&self, sink: T) -> PredecessorIter<V, E> {
+        let sink = *sink.borrow();
+        let sources = match &self.reverse {
+            Some(reverse) => {
+                PredecessorSources::Indexed(reverse.get(sink.index()).map(OrdSet::iter))
+            }
+            None => PredecessorSources::Scan(self.guts.iter()),
+        };
         PredecessorIter {
-            iter: self.guts.iter(),
-            sink: *sink.borrow(),
+            graph: self,
+            sources,
+            sink,
         }
     }
 
+    /// Counts the edges in the PGraph that _end_ at `sink`.
+    ///
+    /// This takes O(V) time, unless the PGraph keeps a reverse index (see `with_reverse_index`), in which case it takes O(in-degree) time.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[2], ids[2], 33);
+    ///
+    /// assert_eq!(g.in_degree(ids[0]), 0);
+    /// assert_eq!(g.in_degree(ids[2]), 3);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn in_degree<T: Borrow<Id>>(&self, sink: T) -> usize {
+        self.predecessors(sink).count()
+    }
+
+    /// Counts the edges in the PGraph that _start_ at `source`, in O(1) time. This is the same as `len_neighbors` on `source`'s
+    /// [Vertex](struct.Vertex.html), except that it returns 0 if `source` isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    ///
+    /// assert_eq!(g.edge_count_from(ids[0]), 2);
+    /// assert_eq!(g.edge_count_from(ids[1]), 0);
+    ///
+    /// g.remove_mut(ids[0]);
+    /// assert_eq!(g.edge_count_from(ids[0]), 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edge_count_from<T: Borrow<Id>>(&self, source: T) -> usize {
+        self.vertex(source).map_or(0, Vertex::len_neighbors)
+    }
+
+    /// Counts the edges in the PGraph that _end_ at `sink`, or returns 0 if `sink` isn't in the PGraph. This is the same as
+    /// `in_degree`, so it takes O(in-degree) time if the PGraph keeps a reverse index (see `with_reverse_index`), and O(V) time otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::with_reverse_index();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    ///
+    /// assert_eq!(g.edge_count_to(ids[2]), 2);
+    /// assert_eq!(g.edge_count_to(ids[0]), 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edge_count_to<T: Borrow<Id>>(&self, sink: T) -> usize {
+        self.in_degree(sink)
+    }
+
+    /// Returns `true` iff `id` is in the PGraph and is a sink: it has at least one edge that _ends_ at it, but none that _start_ at it.
+    /// (See [`Vertex::is_source`](struct.Vertex.html#method.is_source) for the other direction.)
+    ///
+    /// Like `in_degree`, this takes O(V) time unless the PGraph keeps a reverse index.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    ///
+    /// assert!(g[ids[0]].is_source());
+    /// assert!(!g[ids[1]].is_source());
+    /// assert!(g.is_sink(ids[1]));
+    /// assert!(!g.is_sink(ids[0]));
+    /// assert!(!g.is_sink(ids[2]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_sink<T: Borrow<Id>>(&self, id: T) -> bool {
+        let id = id.borrow();
+        matches!(self.vertex(id), Some(v) if !v.is_source())
+            && self.predecessors(id).next().is_some()
+    }
+
     /// Returns an iterator over all the [Id](struct.Id.html)s of vertices in the PGraph with an edge that _ends_ at `sink`.
     #[must_use]
     pub fn predecessor_ids<T: Borrow<Id>>(&self, sink: T) -> PredecessorIdIter<V, E> {
-        PredecessorIter {
-            iter: self.guts.iter(),
-            sink: *sink.borrow(),
-        }
-        .map(|(source, _, _)| source)
+        self.predecessors(sink).map(|(source, _, _)| source)
     }
 
     /// Returns an iterator over all the edges in the PGraph that _start_ at `source`.  
     /// The iterator's items (source: Id, sink: Id, edge: &E)
+    ///
+    /// Like `edges`, the iterator sees the PGraph as it was when the iterator was created, even if a clone of it is changed in the meantime.
     #[must_use]
     pub fn outbound_edges<T: Borrow<Id>>(&self, source: T) -> OutboundIter<E> {
         self.vertex(source)
@@ -458,6 +1403,89 @@ impl<V, E> PGraph<V, E> {
             .flatten()
     }
 
+    /// Returns an iterator over the edges that _start_ at `source` and come after the edge to `after_sink` (see
+    /// [Iteration order](struct.PGraph.html#iteration-order)), so a vertex with a huge number of edges can be paged through by passing
+    /// the last sink of each page. The iterator's items are (source: Id, sink: Id, edge: &E)
+    ///
+    /// Edges are stored by their sink's internal index, so skipping ahead is cheap, and the skip is by that index, not by a count of edges.
+    /// `after_sink` doesn't have to be one of `source`'s sinks, or even still be in the PGraph: every edge to a vertex with a higher index
+    /// than it is yielded.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(0..10usize);
+    /// for &sink in &ids {
+    ///     g.connect_mut(ids[0], sink, 0);
+    /// }
+    ///
+    /// let first: Vec<_> = g.outbound_ids(ids[0]).take(4).collect();
+    /// let second: Vec<_> = g.outbound_edges_from(ids[0], first[3]).take(4).map(|(_, sink, _)| sink).collect();
+    /// assert_eq!(first, ids[..4].to_vec());
+    /// assert_eq!(second, ids[4..8].to_vec());
+    ///
+    /// g.remove_mut(ids[8]);
+    /// let last: Vec<_> = g.outbound_edges_from(ids[0], ids[8]).map(|(_, sink, _)| sink).collect();
+    /// assert_eq!(last, vec![ids[9]]);
+    /// # }
+    /// ```
+    pub fn outbound_edges_from<T: Borrow<Id>>(
+        &self,
+        source: T,
+        after_sink: T,
+    ) -> OutboundIter<'_, E> {
+        let after_sink = *after_sink.borrow();
+        self.vertex(source)
+            .map(|v| NodeEdgeIter {
+                iter: v.neighbors_after(after_sink),
+                source: v.id(),
+            })
+            .into_iter()
+            .flatten()
+    }
+
+    /// Folds `f` over the edges that _start_ at `source`, calling it with the accumulator, the edge's sink, and the edge's weight.
+    ///
+    /// Returns `init` unchanged if `source` isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    ///
+    /// let total = g.fold_neighbors(id1, 0, |total, _, weight| total + weight);
+    /// assert_eq!(total, 25);
+    ///
+    /// let heaviest = g.fold_neighbors(id1, None, |best: Option<(_, usize)>, sink, &weight| match best {
+    ///     Some((_, best_weight)) if best_weight >= weight => best,
+    ///     _ => Some((sink, weight)),
+    /// });
+    /// assert_eq!(heaviest, Some((id3, 13)));
+    /// # }
+    /// ```
+    pub fn fold_neighbors<T: Borrow<Id>, B, F: FnMut(B, Id, &E) -> B>(
+        &self,
+        source: T,
+        init: B,
+        mut f: F,
+    ) -> B {
+        match self.vertex(source) {
+            Some(v) => v
+                .into_iter()
+                .fold(init, |acc, (sink, weight)| f(acc, sink, weight)),
+            None => init,
+        }
+    }
+
     /// Returns an iterator over all the [Id](struct.Id.html)s of vertices in the PGraph with an edge that _starts_ at `source`.
     #[must_use]
     pub fn outbound_ids<T: Borrow<Id>>(&self, source: T) -> OutboundIdIter<E> {
@@ -467,10 +1495,355 @@ impl<V, E> PGraph<V, E> {
             .flatten()
     }
 
-    pub fn edges<'a>(&'a self) -> EdgeIter<'a, V, E> {
+    /// Returns an iterator over the [Id](struct.Id.html)s of every vertex that has an edge either _from_ or _to_
+    /// `id`, as if the PGraph were undirected. Each neighbor is yielded once, even if it's connected in both
+    /// directions. A vertex with a self-loop is its own neighbor.
+    ///
+    /// The outbound neighbors come first, followed by the remaining predecessors.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 21);
+    /// g.connect_mut(id3, id1, 31);
+    ///
+    /// let neighbors: Vec<_> = g.neighbors_undirected(id1).collect();
+    /// assert_eq!(neighbors, vec![id2, id3]);
+    /// # }
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn neighbors_undirected<T: Borrow<Id>>(&self, id: T) -> impl Iterator<Item = Id> + '_ {
+        let id = *id.borrow();
+        self.outbound_ids(id).chain(
+            self.predecessor_ids(id)
+                .filter(move |source| !self.has_edge(&id, source)),
+        )
+    }
+
+    /// Returns an iterator over the edges that touch `id` in the direction `dir`: the edges that _start_ at `id` for
+    /// `Direction::Outgoing`, or the edges that _end_ at `id` for `Direction::Incoming`.
+    /// The iterator's items are (neighbor: Id, edge: &E), where `neighbor` is the vertex on the other end of the edge.
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate petgraph;
+    /// # use pgraph::PGraph;
+    /// # use petgraph::Direction;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id3, id2, 32);
+    ///
+    /// let outgoing: Vec<_> = g.adjacent(id1, Direction::Outgoing).collect();
+    /// assert_eq!(outgoing, vec![(id2, &12)]);
+    ///
+    /// let incoming: Vec<_> = g.adjacent(id2, Direction::Incoming).collect();
+    /// assert_eq!(incoming, vec![(id1, &12), (id3, &32)]);
+    /// # }
+    /// ```
+    pub fn adjacent<T: Borrow<Id>>(&self, id: T, dir: Direction) -> impl Iterator<Item = (Id, &E)> {
+        let id = *id.borrow();
+        let (outgoing, incoming) = match dir {
+            Direction::Outgoing => (Some(self.outbound_edges(id)), None),
+            Direction::Incoming => (None, Some(self.predecessors(id))),
+        };
+        outgoing
+            .into_iter()
+            .flatten()
+            .map(|(_, sink, weight)| (sink, weight))
+            .chain(
+                incoming
+                    .into_iter()
+                    .flatten()
+                    .map(|(source, _, weight)| (source, weight)),
+            )
+    }
+
+    /// Returns the edges that _start_ at `source`, as (sink, weight) pairs sorted by the result of calling `key` on their weights.
+    /// The sort is stable, so edges with equal keys stay in the order `outbound_edges` yields them. Returns an empty Vec if `source`
+    /// isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 5);
+    /// g.connect_mut(id1, id1, 11);
+    ///
+    /// let cheapest_first = g.neighbors_sorted_by(id1, |&weight| weight);
+    /// assert_eq!(cheapest_first, vec![(id3, &5), (id1, &11), (id2, &12)]);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn neighbors_sorted_by<T: Borrow<Id>, K: Ord, F: FnMut(&E) -> K>(
+        &self,
+        source: T,
+        mut key: F,
+    ) -> Vec<(Id, &E)> {
+        let mut neighbors: Vec<_> = self
+            .outbound_edges(source)
+            .map(|(_, sink, weight)| (sink, weight))
+            .collect();
+        neighbors.sort_by_key(|&(_, weight)| key(weight));
+        neighbors
+    }
+
+    /// Returns true iff `id` is in the PGraph and has an edge to itself.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id1, 11);
+    /// g.connect_mut(id2, id1, 21);
+    ///
+    /// assert!(g.has_self_loop(id1));
+    /// assert!(!g.has_self_loop(id2));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn has_self_loop<T: Borrow<Id>>(&self, id: T) -> bool {
+        let id = id.borrow();
+        self.has_edge(id, id)
+    }
+
+    /// Returns an iterator over every self-loop (an edge from a vertex to itself) in the PGraph.
+    /// The iterator's items are (vertex: Id, edge: &E)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id1, 11);
+    /// g.connect_mut(id2, id1, 21);
+    ///
+    /// let loops: Vec<_> = g.self_loops().collect();
+    /// assert_eq!(loops, vec![(id1, &11)]);
+    /// # }
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn self_loops(&self) -> impl Iterator<Item = (Id, &E)> {
+        self.into_iter()
+            .filter_map(|v| v.weight(v.id()).map(|weight| (v.id(), weight)))
+    }
+
+    /// Frees the empty slots left at the end of the PGraph's underlying storage by removing its highest-index vertices, in-place.
+    ///
+    /// Empty slots between live vertices are left alone, so every existing [Id](struct.Id.html) stays valid.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    ///
+    /// g.remove_all_mut(&ids[1..]);
+    /// g.shrink_to_fit_mut();
+    ///
+    /// assert_eq!(g[(ids[0],)], 1);
+    /// let id = g.add_mut(5);
+    /// assert_eq!(g[(id,)], 5);
+    /// # }
+    /// ```
+    pub fn shrink_to_fit_mut(&mut self) {
+        while let Some(None) = self.guts.last() {
+            let index = self.guts.len() - 1;
+            self.guts.pop_back();
+            self.empties.remove(&index);
+        }
+        if let Some(reverse) = &mut self.reverse {
+            while reverse.len() > self.guts.len() {
+                reverse.pop_back();
+            }
+        }
+    }
+
+    /// Trims the empty slots at the end of every vertex's adjacency list, in-place, without removing any edges.
+    /// (See [`Vertex::shrink_neighbors`](struct.Vertex.html#method.shrink_neighbors).)
+    ///
+    /// Empty slots before a vertex's last edge are left alone, so every edge stays where it is. Vertices that have nothing to free
+    /// aren't touched, so they keep sharing structure with the PGraph's clones.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[1], ids[0], 21);
+    /// g.remove_mut(ids[2]);
+    /// let before = g.structural_stats().adjacency_slots;
+    ///
+    /// g.shrink_adjacencies_mut();
+    /// assert!(g.structural_stats().adjacency_slots < before);
+    /// assert_eq!(g[(ids[1], ids[0])], 21);
+    /// # }
+    /// ```
+    pub fn shrink_adjacencies_mut(&mut self) {
+        let shrinkable: Vec<Id> = self
+            .into_iter()
+            .filter(|vertex| vertex.can_shrink_neighbors())
+            .map(Vertex::id)
+            .collect();
+        for id in shrinkable {
+            if let Some((vertex, _)) = self.vertex_and_components_mut(id) {
+                vertex.shrink_neighbors();
+            }
+        }
+    }
+
+    /// Returns an iterator over the [Id](struct.Id.html)s of every isolated vertex in the PGraph, i.e. every vertex with no outgoing
+    /// and no incoming edges. (A vertex whose only edge is a self-loop isn't isolated.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// let id4 = g.add_mut(4);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id3, id3, 33);
+    ///
+    /// let isolated: Vec<_> = g.isolated_vertices().collect();
+    /// assert_eq!(isolated, vec![id4]);
+    /// # }
+    /// ```
+    pub fn isolated_vertices(&self) -> impl Iterator<Item = Id> + '_ {
+        let mut connected = vec![false; self.guts.len()];
+        for (source, sink, _) in self.edges() {
+            connected[source.index()] = true;
+            connected[sink.index()] = true;
+        }
+        self.ids().filter(move |id| !connected[id.index()])
+    }
+
+    /// Counts the edges in the PGraph.
+    ///
+    /// Every vertex keeps a count of its outgoing edges, so this runs in O(N), where N is the number of vertex slots,
+    /// instead of walking every edge. (This is the equivalent of `petgraph`'s `EdgeCount` trait, which the version of
+    /// `petgraph` we depend on doesn't provide.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 21);
+    /// g.connect_mut(id2, id2, 22);
+    /// assert_eq!(3, g.edge_count());
+    ///
+    /// g.remove_mut(id1);
+    /// assert_eq!(1, g.edge_count());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.into_iter().map(Vertex::len_neighbors).sum()
+    }
+
+    /// Returns an iterator over all the edges in the PGraph, ordered by source and then by sink.
+    /// (See [Iteration order](struct.PGraph.html#iteration-order).)
+    /// The iterator's items are (source: Id, sink: Id, edge: &E)
+    ///
+    /// The iterator borrows this PGraph, so the PGraph itself can't change while it's in use, but a clone of the PGraph can. Since the
+    /// clone copies whatever parts of the shared structure it changes, the iterator still sees the edges as they were when it was created.
+    /// (Use `snapshot_edges` to keep a copy of the edges while changing the PGraph itself.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id2, id1, 21);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id2, 22);
+    ///
+    /// let edges: Vec<_> = g.edges().collect();
+    /// assert_eq!(edges, vec![(id1, id2, &12), (id2, id1, &21), (id2, id2, &22)]);
+    ///
+    /// let mut h = g.clone();
+    /// let mut edges = g.edges();
+    /// h.disconnect_mut(id2, id1);
+    /// h.connect_mut(id1, id1, 11);
+    /// assert_eq!(edges.next(), Some((id1, id2, &12)));
+    /// assert_eq!(edges.next(), Some((id2, id1, &21)));
+    /// # }
+    /// ```
+    pub fn edges<'a>(&'a self) -> EdgeIter<'a, V, E> {
         let func: fn(&'a Vertex<V, E>) -> NodeEdgeIter<'a, E> = NodeEdgeIter::from;
         self.into_iter().map(func).flatten()
     }
+
+    /// Returns an iterator over all the edges in the PGraph, ordered by sink and then by source: first every edge that _ends_ at the
+    /// first vertex, then every edge that ends at the second, and so on.
+    /// The iterator's items are (source: Id, sink: Id, edge: &E)
+    ///
+    /// This calls `predecessors` for each vertex in turn, so unless the PGraph has a reverse index (see `with_reverse_index`), it
+    /// takes O(V) time per vertex, on top of the time spent on the edges themselves.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id2, id1, 21);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id2, 22);
+    ///
+    /// let edges: Vec<_> = g.edges_by_sink().collect();
+    /// assert_eq!(edges, vec![(id2, id1, &21), (id1, id2, &12), (id2, id2, &22)]);
+    /// # }
+    /// ```
+    pub fn edges_by_sink(&self) -> impl Iterator<Item = (Id, Id, &E)> {
+        self.ids().flat_map(move |sink| self.predecessors(sink))
+    }
 }
 
 pub type IdIter<'a, V, E> = FilterMap<
@@ -481,23 +1854,38 @@ pub type IdIter<'a, V, E> = FilterMap<
 pub type PredecessorIdIter<'a, V, E> = Map<PredecessorIter<'a, V, E>, fn((Id, Id, &'a E)) -> Id>;
 
 pub struct PredecessorIter<'a, V, E> {
-    iter: im::vector::Iter<'a, Option<Vertex<V, E>>>,
+    graph: &'a PGraph<V, E>,
+    sources: PredecessorSources<'a, V, E>,
     sink: Id,
 }
 
+/// The vertices a `PredecessorIter` checks for edges to its sink.
+enum PredecessorSources<'a, V, E> {
+    /// Every vertex in the PGraph
+    Scan(im::vector::Iter<'a, Option<Vertex<V, E>>>),
+    /// The possible predecessors recorded in the PGraph's reverse index
+    Indexed(Option<im::ordset::Iter<'a, Id>>),
+}
+
 impl<'a, V, E> Iterator for PredecessorIter<'a, V, E> {
     type Item = (Id, Id, &'a E);
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.iter.next() {
-                None => break None,
-                Some(Some(source)) => {
-                    if let Some(e) = source.weight(self.sink) {
-                        break Some((source.id(), self.sink, e));
+            let source = match &mut self.sources {
+                PredecessorSources::Scan(iter) => match iter.next()? {
+                    Some(source) => source,
+                    None => continue,
+                },
+                PredecessorSources::Indexed(iter) => {
+                    match self.graph.vertex(iter.as_mut()?.next()?) {
+                        Some(source) => source,
+                        None => continue,
                     }
                 }
-                _ => (),
+            };
+            if let Some(e) = source.weight(self.sink) {
+                break Some((source.id(), self.sink, e));
             }
         }
     }
@@ -565,7 +1953,148 @@ impl<V: Clone, E> PGraph<V, E> {
     /// ```
     #[must_use]
     pub fn vertex_data_mut<T: Borrow<Id>>(&mut self, id: T) -> Option<&mut V> {
-        self.vertex_mut(id).map(|v| v.data_mut())
+        let (vertex, _) = self.vertex_and_components_mut(*id.borrow())?;
+        Some(vertex.data_mut())
+    }
+
+    /// Replaces the data on the vertex `id` with `data`, in-place, and returns the data it held before. Returns `None` (and drops `data`)
+    /// if such a vertex cannot be found.
+    ///
+    /// The old data is only cloned if a clone of the PGraph still refers to it; otherwise it's moved out.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<String, ()>::new();
+    /// let id = g.add_mut("pending".to_string());
+    /// let h = g.clone();
+    ///
+    /// assert_eq!(g.replace_vertex_data(id, "running".to_string()), Some("pending".to_string()));
+    /// assert_eq!(g.replace_vertex_data(id, "done".to_string()), Some("running".to_string()));
+    /// assert_eq!(g[(id,)], "done");
+    /// assert_eq!(h[(id,)], "pending");
+    ///
+    /// g.remove_mut(id);
+    /// assert_eq!(g.replace_vertex_data(id, "lost".to_string()), None);
+    /// # }
+    /// ```
+    pub fn replace_vertex_data<T: Borrow<Id>>(&mut self, id: T, data: V) -> Option<V> {
+        let (vertex, _) = self.vertex_and_components_mut(*id.borrow())?;
+        let old = vertex.replace_data(Arc::new(data));
+        Some(Arc::try_unwrap(old).unwrap_or_else(|shared| (*shared).clone()))
+    }
+
+    /// Swaps the data on two different vertices, in-place. All of the PGraph's edges and [Id](struct.Id.html)s stay exactly as they were.
+    ///
+    /// This only swaps the vertices' handles to their data, so the data itself is never cloned.
+    ///
+    /// Returns `false` (and does nothing) if `a` and `b` are the same [Id](struct.Id.html) or if either of them isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, usize>::new();
+    /// let id1 = g.add_mut("first");
+    /// let id2 = g.add_mut("second");
+    /// g.connect_mut(id1, id2, 12);
+    ///
+    /// assert!(g.swap_vertex_data(id1, id2));
+    /// assert_eq!(g[(id1,)], "second");
+    /// assert_eq!(g[(id2,)], "first");
+    /// assert!(g.has_edge(id1, id2));
+    ///
+    /// assert!(!g.swap_vertex_data(id1, id1));
+    /// # }
+    /// ```
+    pub fn swap_vertex_data<T: Borrow<Id>>(&mut self, a: T, b: T) -> bool {
+        let (a, b) = (a.borrow(), b.borrow());
+        if a == b || !self.has_vertex(b) {
+            return false;
+        }
+
+        match self.vertex(a).map(Vertex::shared_data) {
+            Some(a_data) => {
+                let b_data = self.vertex_keeping_components_mut(*b).replace_data(a_data);
+                self.vertex_keeping_components_mut(*a).replace_data(b_data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pre-extends the adjacency list of `source` with empty slots for edges to every vertex with an index up to and including
+    /// `up_to_index`, in-place, so connecting `source` to those vertices later doesn't have to extend it. Has no effect on the PGraph's
+    /// edges. `up_to_index` is clamped to the PGraph's highest slot index, since no vertex can be past it.
+    ///
+    /// Panics if `source` is not in the PGraph
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(0..100usize);
+    ///
+    /// let last = ids.len() - 1;
+    /// g.reserve_neighbors(ids[0], last);
+    /// for &sink in &ids {
+    ///     g.connect_mut(ids[0], sink, 1);
+    /// }
+    /// assert_eq!(g.outbound_ids(ids[0]).count(), 100);
+    /// # }
+    /// ```
+    pub fn reserve_neighbors<T: Borrow<Id>>(&mut self, source: T, up_to_index: usize) {
+        let up_to_index = self.clamp_index(up_to_index);
+        self.vertex_keeping_components_mut(*source.borrow())
+            .reserve_neighbors(up_to_index);
+    }
+
+    /// Gets mutable references to the data of two different [Vertices](struct.Vertex.html) at once.
+    ///
+    /// Returns `None` if `a` and `b` are the same [Id](struct.Id.html) or if either of them isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, ()>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// let (data1, data2) = g.vertex_data_pair_mut(id1, id2).unwrap();
+    /// std::mem::swap(data1, data2);
+    /// *data1 *= 10;
+    ///
+    /// assert_eq!(g[(id1,)], 20);
+    /// assert_eq!(g[(id2,)], 1);
+    /// assert!(g.vertex_data_pair_mut(id1, id1).is_none());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn vertex_data_pair_mut<T: Borrow<Id>>(&mut self, a: T, b: T) -> Option<(&mut V, &mut V)> {
+        let (a, b) = (a.borrow(), b.borrow());
+        if a == b || !self.has_vertex(a) || !self.has_vertex(b) {
+            return None;
+        }
+
+        // Split `guts` at the later slot, so each half can hand out a mutable reference to one of the two vertices.
+        let (first, second) = if a.index() < b.index() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let (left, right) = self.guts.focus_mut().split_at(second.index());
+        let first = left.narrow(first.index()..).into_iter().next()?.as_mut()?;
+        let second = right.into_iter().next()?.as_mut()?;
+
+        let (first, second) = (first.data_mut(), second.data_mut());
+        if a.index() < b.index() {
+            Some((first, second))
+        } else {
+            Some((second, first))
+        }
     }
 
     /// Creates an edge from `source` to `sink`. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
@@ -593,152 +2122,592 @@ impl<V: Clone, E> PGraph<V, E> {
     /// # }
     /// ```
     #[must_use]
-    pub fn connect<T: Borrow<Id>>(&self, source: T, sink: T, weight: E) -> Self {
-        let mut result = self.clone();
-        result.connect_mut(source, sink, weight);
-        result
+    pub fn connect<T: Borrow<Id>>(&self, source: T, sink: T, weight: E) -> Self {
+        let mut result = self.clone();
+        result.connect_mut(source, sink, weight);
+        result
+    }
+
+    /// Tries to create an edge from `source` to `sink`. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+    ///
+    /// Returns the new, modified version of the PGraph, or `None` if `source` and/or `sink` is not in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let g = PGraph::<usize, usize>::new();
+    ///
+    /// let (g, id1) = g.add(1);
+    /// let (g, id2) = g.add(2);
+    /// let (g, id3) = g.add(3);
+    ///
+    /// let maybe_g = g.try_connect(id1, id2, 12);
+    /// assert!(maybe_g.is_some());
+    /// let g = maybe_g.unwrap();
+    ///
+    /// let maybe_g = g.try_connect(id2, bad_id, 20);
+    /// assert!(maybe_g.is_none());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn try_connect<T: Borrow<Id>>(&self, source: T, sink: T, weight: E) -> Option<Self> {
+        let source = source.borrow();
+        let sink = sink.borrow();
+
+        let mut result = Cow::Borrowed(self);
+        if result.has_vertex(source)
+            && result.has_vertex(sink)
+            && !result.closes_cycle(*source, *sink)
+        {
+            result.to_mut().connect_mut(source, sink, weight);
+            Some(result.into_owned())
+        } else {
+            None
+        }
+    }
+
+    /// Creates an edge from `source` to `sink`, in-place. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+    ///
+    /// Panics if `source` and/or `sink` is not in the PGraph, or if the PGraph was created by `dag_checked` and the edge would
+    /// introduce a cycle.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut  g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    /// g.connect_mut(id1, id3, 13);
+    ///
+    /// assert_eq!(g[(id1, id2)], 12);
+    /// assert_eq!(g[(id2, id3)], 23);
+    /// assert_eq!(g[(id1, id3)], 13);
+    /// # }
+    /// ```
+    pub fn connect_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, weight: E) {
+        let sink = sink.borrow();
+
+        if self.has_vertex(sink) {
+            let source = self[source].id();
+            if self.closes_cycle(source, *sink) {
+                panic!(
+                    "An edge from {:?} to {:?} would introduce a cycle.",
+                    source, sink
+                )
+            }
+            self.connect_unchecked_mut(source, *sink, weight);
+        } else {
+            panic!(
+                "The sink vertex with Id {:?} was not found in the graph.",
+                sink
+            )
+        }
+    }
+
+    /// Creates an edge from `source` to `sink`, in-place. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+    ///
+    /// Returns `true` iff there wasn't already an edge from `source` to `sink`. (See `upsert_edge_mut` for a more detailed report.)
+    /// If the PGraph was created by `dag_checked` and the edge would introduce a cycle, returns `false` and changes nothing.
+    ///
+    /// Panics if `source` and/or `sink` is not in the PGraph
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// assert!(g.insert_edge_mut(id1, id2, 12));
+    /// assert!(!g.insert_edge_mut(id1, id2, 21));
+    /// assert_eq!(g[(id1, id2)], 21);
+    /// # }
+    /// ```
+    pub fn insert_edge_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, weight: E) -> bool {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if self.closes_cycle(source, sink) {
+            return false;
+        }
+
+        let created = !self.has_edge(source, sink);
+        self.connect_mut(source, sink, weight);
+        created
+    }
+
+    /// Creates an edge from `source` to `sink`, in-place, unless there already is one. An existing edge keeps its weight.
+    /// (Vertices can have edges to themselves.)
+    ///
+    /// Returns `true` iff the edge was created. If the PGraph was created by `dag_checked` and the edge would introduce a cycle,
+    /// returns `false` and changes nothing. Panics if `source` and/or `sink` is not in the PGraph
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// assert!(g.connect_if_absent_mut(id1, id2, 12));
+    /// assert!(!g.connect_if_absent_mut(id1, id2, 21));
+    /// assert_eq!(g[(id1, id2)], 12);
+    /// # }
+    /// ```
+    pub fn connect_if_absent_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, weight: E) -> bool {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if self.has_edge(source, sink) || self.closes_cycle(source, sink) {
+            false
+        } else {
+            self.connect_mut(source, sink, weight);
+            true
+        }
+    }
+
+    /// Creates an edge from `source` to `sink`, in-place, unless they're the same vertex. If there already exists an edge, it will be
+    /// overwritten. Useful for keeping a PGraph free of self-loops when building it, instead of calling `remove_self_loops_mut` afterward.
+    ///
+    /// Returns `true` iff the edge was created or overwritten. Returns `false` (and changes nothing) if `source` and `sink` are equal,
+    /// or if the PGraph was created by `dag_checked` and the edge would introduce a cycle; otherwise, panics if `source` and/or `sink`
+    /// is not in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// assert!(g.connect_no_self_loop_mut(id1, id2, 12));
+    /// assert!(!g.connect_no_self_loop_mut(id1, id1, 11));
+    /// assert_eq!(g.edge_count(), 1);
+    /// assert!(!g.has_self_loop(id1));
+    /// # }
+    /// ```
+    pub fn connect_no_self_loop_mut<T: Borrow<Id>>(
+        &mut self,
+        source: T,
+        sink: T,
+        weight: E,
+    ) -> bool {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if source == sink || self.closes_cycle(source, sink) {
+            false
+        } else {
+            self.connect_mut(source, sink, weight);
+            true
+        }
+    }
+
+    /// Adds two new vertices with the given data and creates an edge between them, in-place. Always adds new vertices, even if there
+    /// are already vertices with the same data. (Use `from_edges` to build a PGraph whose vertices are deduplicated by their data.)
+    ///
+    /// Returns the [Id](struct.Id.html)s of the new vertices, as (source, sink).
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, usize>::new();
+    ///
+    /// let (a, b) = g.connect_or_create_mut("a", "b", 1);
+    /// let (c, d) = g.connect_or_create_mut("a", "b", 2);
+    /// assert_eq!(g.ids().collect::<Vec<_>>(), vec![a, b, c, d]);
+    /// assert_eq!(g[(a, b)], 1);
+    /// assert_eq!(g[(c, d)], 2);
+    /// # }
+    /// ```
+    pub fn connect_or_create_mut(&mut self, source_data: V, sink_data: V, weight: E) -> (Id, Id) {
+        let source = self.add_mut(source_data);
+        let sink = self.add_mut(sink_data);
+        self.connect_mut(source, sink, weight);
+        (source, sink)
+    }
+
+    /// Tries to create an edge from `source` to `sink`, in-place. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+    ///
+    /// Returns a [ConnectError](enum.ConnectError.html) naming the missing vertex if the edge couldn't be created.
+    /// If both `source` and `sink` are missing, the error names `source`. If the PGraph was created by `dag_checked`, an edge that
+    /// would introduce a cycle is rejected with `ConnectError::WouldCycle`.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::{ConnectError, PGraph};
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// assert_eq!(g.connect_checked(id1, id2, 12), Ok(()));
+    /// assert_eq!(12, g[(id1, id2)]);
+    ///
+    /// assert_eq!(g.connect_checked(id2, bad_id, 20), Err(ConnectError::SinkMissing(bad_id)));
+    /// assert_eq!(g.connect_checked(bad_id, id1, 1), Err(ConnectError::SourceMissing(bad_id)));
+    /// assert_eq!(g.connect_checked(bad_id, bad_id, 0), Err(ConnectError::SourceMissing(bad_id)));
+    /// # }
+    /// ```
+    pub fn connect_checked<T: Borrow<Id>>(
+        &mut self,
+        source: T,
+        sink: T,
+        weight: E,
+    ) -> Result<(), ConnectError> {
+        let (source, sink) = (source.borrow(), sink.borrow());
+
+        if !self.has_vertex(source) {
+            Err(ConnectError::SourceMissing(*source))
+        } else if !self.has_vertex(sink) {
+            Err(ConnectError::SinkMissing(*sink))
+        } else if self.closes_cycle(*source, *sink) {
+            Err(ConnectError::WouldCycle(*source, *sink))
+        } else {
+            self.connect_unchecked_mut(*source, *sink, weight);
+            Ok(())
+        }
+    }
+
+    /// Tries to create an edge from `source` to `sink`. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+    ///
+    /// Returns `false` iff the edge couldn't be created (i.e. `source` and/or `sink` is not in the PGraph, or the PGraph was created
+    /// by `dag_checked` and the edge would introduce a cycle)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// let it_worked = g.try_connect_mut(id1, id2, 12);
+    /// assert!(it_worked);
+    /// assert_eq!(12, g[(id1, id2)]);
+    ///
+    /// let it_worked = g.try_connect_mut(id2, bad_id, 20);
+    /// assert!(!it_worked);
+    /// # }
+    /// ```
+    pub fn try_connect_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, weight: E) -> bool {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if self.has_vertex(source) && self.has_vertex(sink) && !self.closes_cycle(source, sink) {
+            self.connect_unchecked_mut(source, sink, weight);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Gets a mutable reference to the [Vertex](struct.Vertex.html) corresponding to a given [Id](struct.Id.html). Will return `None` if one cannot be found.
+    ///
+    /// Some reasons this could occur are:
+    /// -   This `Id` is from a `PGraph` that isn't an ancestor of the current `PGraph`
+    /// -   The `Vertex` corresponding to this `Id` has been removed from the `PGraph`
+    ///
+    /// If the PGraph keeps track of its components (see `with_union_find`), they're rebuilt by the next query, since the vertex's
+    /// edges can be removed through the returned reference. Use `vertex_data_mut` or `weight_mut` for changes that don't need that.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// let maybe_v = g.vertex_mut(bad_id);
+    /// assert!(maybe_v.is_none());
+    ///
+    /// let maybe_v = g.vertex_mut(id2);
+    /// assert!(maybe_v.is_some());
+    /// *maybe_v.unwrap().data_mut() *= 10;
+    /// assert_eq!(20, g[(id2,)])
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn vertex_mut<T: Borrow<Id>>(&mut self, id: T) -> Option<&mut Vertex<V, E>> {
+        self.invalidate_components();
+        match self.guts.get_mut(id.borrow().index()) {
+            Some(Some(vertex)) if vertex.same_id(id) => Some(vertex),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over mutable references to every [Vertex](struct.Vertex.html) in the PGraph, in the same order as `ids`.
+    ///
+    /// Walking the vertices mutably stops this PGraph from sharing its vertex storage with its clones, so it copies that storage
+    /// (but not the data or weights) if it's shared. The data and weights themselves are only copied when they're changed: `data_mut`
+    /// and `weight_mut` use `Arc::make_mut`, which clones the data or weight first if a clone of the PGraph still refers to it.
+    /// Like `vertex_mut`, this makes a PGraph created by `with_union_find` rebuild its components on the next query.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[1], ids[1], 22);
+    ///
+    /// let h = g.clone();
+    /// for vertex in g.vertices_mut() {
+    ///     *vertex.data_mut() *= 10;
+    ///     let id = vertex.id();
+    ///     vertex.disconnect(id);
+    /// }
+    ///
+    /// assert_eq!(g.iter_data().cloned().collect::<Vec<_>>(), vec![10, 20, 30]);
+    /// assert!(!g.has_self_loop(ids[1]));
+    /// assert_eq!(h.iter_data().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// assert!(h.has_self_loop(ids[1]));
+    /// # }
+    /// ```
+    pub fn vertices_mut(&mut self) -> impl Iterator<Item = &mut Vertex<V, E>> {
+        self.invalidate_components();
+        self.guts.iter_mut().flatten()
+    }
+}
+
+impl<V: Clone + Eq + Hash, E> PGraph<V, E> {
+    /// Creates a new PGraph from a list of edges, given as (source data: V, sink data: V, weight: E).
+    ///
+    /// Vertices are created as they're first mentioned, and are identified by their data: every occurrence of an equal
+    /// `V` refers to the same vertex. If the same (source, sink) pair appears more than once, the last weight wins,
+    /// like calling `connect_mut` repeatedly. (Use [`from_edges_with`](struct.PGraph.html#method.from_edges_with) to combine
+    /// the weights instead.)
+    ///
+    /// Returns the new PGraph and the (source: Id, sink: Id) of each item in `edges`, in the same order (so a repeated pair
+    /// shows up once per occurrence).
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let (g, pairs) = PGraph::from_edges(vec![
+    ///     ("Phoenix", "Durango", 1632),
+    ///     ("Durango", "Reno", 1184),
+    ///     ("Phoenix", "Durango", 1000),
+    /// ]);
+    ///
+    /// assert_eq!(g.ids().count(), 3);
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(pairs[0], pairs[2]);
+    ///
+    /// let (phoenix, durango) = pairs[0];
+    /// assert_eq!(g[phoenix].data(), &"Phoenix");
+    /// assert_eq!(g[(phoenix, durango)], 1000);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_edges<I: IntoIterator<Item = (V, V, E)>>(edges: I) -> (Self, Vec<(Id, Id)>) {
+        Self::from_edges_with(edges, |old, new| *old = new)
     }
 
-    /// Tries to create an edge from `source` to `sink`. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
-    ///
-    /// Returns the new, modified version of the PGraph, or `None` if `source` and/or `sink` is not in the PGraph.
+    /// Creates a new PGraph from a list of edges, like [`from_edges`](struct.PGraph.html#method.from_edges), except that when
+    /// the same (source, sink) pair appears more than once, `combine` is called with the weight so far and the new weight to
+    /// merge them. (`from_edges` is this with a `combine` that overwrites the old weight.)
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
-    /// let g = PGraph::<usize, usize>::new();
-    ///
-    /// let (g, id1) = g.add(1);
-    /// let (g, id2) = g.add(2);
-    /// let (g, id3) = g.add(3);
-    ///
-    /// let maybe_g = g.try_connect(id1, id2, 12);
-    /// assert!(maybe_g.is_some());
-    /// let g = maybe_g.unwrap();
+    /// let (g, pairs) = PGraph::from_edges_with(
+    ///     vec![("a", "b", 1), ("b", "c", 2), ("a", "b", 3)],
+    ///     |old, new| *old += new,
+    /// );
     ///
-    /// let maybe_g = g.try_connect(id2, bad_id, 20);
-    /// assert!(maybe_g.is_none());
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(g[pairs[0]], 4);
     /// # }
     /// ```
     #[must_use]
-    pub fn try_connect<T: Borrow<Id>>(&self, source: T, sink: T, weight: E) -> Option<Self> {
-        let source = source.borrow();
-        let sink = sink.borrow();
+    pub fn from_edges_with<I: IntoIterator<Item = (V, V, E)>, F: FnMut(&mut E, E)>(
+        edges: I,
+        mut combine: F,
+    ) -> (Self, Vec<(Id, Id)>) {
+        let mut graph = Self::new();
+        let mut ids: HashMap<V, Id> = HashMap::new();
+        let mut weights: HashMap<(Id, Id), E> = HashMap::new();
+        let mut pairs = Vec::new();
+
+        for (source, sink, weight) in edges {
+            let source = *ids
+                .entry(source.clone())
+                .or_insert_with(|| graph.add_mut(source));
+            let sink = *ids
+                .entry(sink.clone())
+                .or_insert_with(|| graph.add_mut(sink));
+            match weights.entry((source, sink)) {
+                Entry::Occupied(mut old) => combine(old.get_mut(), weight),
+                Entry::Vacant(slot) => {
+                    slot.insert(weight);
+                }
+            }
+            pairs.push((source, sink));
+        }
 
-        let mut result = Cow::Borrowed(self);
-        if result.has_vertex(source) && result.has_vertex(sink) {
-            result.to_mut().connect_mut(source, sink, weight);
-            Some(result.into_owned())
-        } else {
-            None
+        for ((source, sink), weight) in weights {
+            graph.connect_mut(source, sink, weight);
         }
+        (graph, pairs)
     }
+}
 
-    /// Creates an edge from `source` to `sink`, in-place. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+impl<V, E: Clone> PGraph<V, E> {
+    /// Copies every edge in the PGraph into a `Vec`, in the same order as `edges`. The items are (source: Id, sink: Id, edge: E)
     ///
-    /// Panics if `source` and/or `sink` is not in the PGraph
+    /// Unlike the iterator from `edges`, the result doesn't borrow the PGraph, so it can be kept while the PGraph is changed.
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// let mut  g = PGraph::<usize, usize>::new();
-    ///
+    /// let mut g = PGraph::<usize, usize>::new();
     /// let id1 = g.add_mut(1);
     /// let id2 = g.add_mut(2);
-    /// let id3 = g.add_mut(3);
-    ///
     /// g.connect_mut(id1, id2, 12);
-    /// g.connect_mut(id2, id3, 23);
-    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id2, id1, 21);
     ///
-    /// assert_eq!(g[(id1, id2)], 12);
-    /// assert_eq!(g[(id2, id3)], 23);
-    /// assert_eq!(g[(id1, id3)], 13);
+    /// let snapshot = g.snapshot_edges();
+    /// for (source, sink, weight) in &snapshot {
+    ///     g.disconnect_mut(source, sink);
+    ///     g.connect_mut(*sink, *source, weight + 100);
+    /// }
+    ///
+    /// assert_eq!(snapshot, vec![(id1, id2, 12), (id2, id1, 21)]);
+    /// assert_eq!(g.snapshot_edges(), vec![(id1, id2, 121)]);
     /// # }
     /// ```
-    pub fn connect_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, weight: E) {
-        let sink = sink.borrow();
-
-        if self.has_vertex(sink) {
-            self[source].connect_to(sink, weight);
-        } else {
-            panic!(
-                "The sink vertex with Id {:?} was not found in the graph.",
-                sink
-            )
-        }
+    #[must_use]
+    pub fn snapshot_edges(&self) -> Vec<(Id, Id, E)> {
+        self.edges()
+            .map(|(source, sink, weight)| (source, sink, weight.clone()))
+            .collect()
     }
 
-    /// Tries to create an edge from `source` to `sink`. If there already exists an edge, it will be overwritten. (Vertices can have edges to themselves.)
+    /// Returns an iterator over all the edges in the PGraph that _end_ at `sink`, with mutable references to their weights.
+    /// The iterator's items are (source: Id, edge: &mut E)
     ///
-    /// Returns `false` iff the edge couldn't be created (i.e. `source` and/or `sink` is not in the PGraph)
+    /// The predecessors are found the same way as `predecessor_ids`, so this takes O(V) time unless the PGraph keeps a reverse index.
+    /// Only the predecessors are then reached mutably: they (and the parts of the vertex storage holding them) stop sharing structure
+    /// with the PGraph's clones, and so do the weights that are yielded. Every other vertex stays shared.
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
     /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[2], ids[0], 31);
+    ///
+    /// let h = g.clone();
+    /// for (_, weight) in g.predecessors_mut(ids[2]) {
+    ///     *weight += 100;
+    /// }
     ///
-    /// let id1 = g.add_mut(1);
-    /// let id2 = g.add_mut(2);
-    /// let id3 = g.add_mut(3);
-    ///
-    /// let it_worked = g.try_connect_mut(id1, id2, 12);
-    /// assert!(it_worked);
-    /// assert_eq!(12, g[(id1, id2)]);
-    ///
-    /// let it_worked = g.try_connect_mut(id2, bad_id, 20);
-    /// assert!(!it_worked);
+    /// assert_eq!(g.weight(ids[0], ids[2]), Some(&113));
+    /// assert_eq!(g.weight(ids[1], ids[2]), Some(&123));
+    /// assert_eq!(g.weight(ids[2], ids[0]), Some(&31));
+    /// assert_eq!(h.weight(ids[0], ids[2]), Some(&13));
     /// # }
     /// ```
-    pub fn try_connect_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, weight: E) -> bool {
-        let sink = sink.borrow();
+    pub fn predecessors_mut<T: Borrow<Id>>(
+        &mut self,
+        sink: T,
+    ) -> impl Iterator<Item = (Id, &mut E)> {
+        let sink = *sink.borrow();
+        let mut sources: Vec<usize> = self.predecessor_ids(sink).map(|id| id.index()).collect();
+        sources.sort_unstable();
 
-        if self.has_vertex(sink) {
-            if let Some(v) = self.vertex_mut(source) {
-                v.connect_to(sink, weight);
-                return true;
-            }
-        };
-        false
+        slots_mut(&mut self.guts, &sources)
+            .into_iter()
+            .filter_map(|v_opt| v_opt.as_mut())
+            .filter_map(move |v| {
+                let source = v.id();
+                v.weight_mut(sink).map(|weight| (source, weight))
+            })
     }
 
-    /// Gets a mutable reference to the [Vertex](struct.Vertex.html) corresponding to a given [Id](struct.Id.html). Will return `None` if one cannot be found.
+    /// Gets mutable references to the weights of `N` different edges at once. Each edge is given as a `(source, sink)` pair.
     ///
-    /// Some reasons this could occur are:
-    /// -   This `Id` is from a `PGraph` that isn't an ancestor of the current `PGraph`
-    /// -   The `Vertex` corresponding to this `Id` has been removed from the `PGraph`
+    /// Returns `None` if any of the edges doesn't exist or if the same edge is asked for more than once.
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
     /// let mut g = PGraph::<usize, usize>::new();
-    ///
     /// let id1 = g.add_mut(1);
     /// let id2 = g.add_mut(2);
-    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 21);
     ///
-    /// let maybe_v = g.vertex_mut(bad_id);
-    /// assert!(maybe_v.is_none());
+    /// let [w12, w21] = g.weights_many_mut([(id1, id2), (id2, id1)]).unwrap();
+    /// std::mem::swap(w12, w21);
     ///
-    /// let maybe_v = g.vertex_mut(id2);
-    /// assert!(maybe_v.is_some());
-    /// *maybe_v.unwrap().data_mut() *= 10;
-    /// assert_eq!(20, g[(id2,)])
+    /// assert_eq!(g.weight(id1, id2), Some(&21));
+    /// assert_eq!(g.weight(id2, id1), Some(&12));
+    /// assert!(g.weights_many_mut([(id1, id2), (id1, id2)]).is_none());
+    /// assert!(g.weights_many_mut([(id1, id2), (id1, id1)]).is_none());
     /// # }
     /// ```
     #[must_use]
-    pub fn vertex_mut<T: Borrow<Id>>(&mut self, id: T) -> Option<&mut Vertex<V, E>> {
-        match self.guts.get_mut(id.borrow().index()) {
-            Some(Some(vertex)) if vertex.same_id(id) => Some(vertex),
-            _ => None,
+    pub fn weights_many_mut<const N: usize, T: Borrow<Id>>(
+        &mut self,
+        edges: [(T, T); N],
+    ) -> Option<[&mut E; N]> {
+        let edges = edges.map(|(source, sink)| (*source.borrow(), *sink.borrow()));
+        for (i, edge) in edges.iter().enumerate() {
+            if edges[..i].contains(edge) || !self.has_edge(edge.0, edge.1) {
+                return None;
+            }
+        }
+
+        // Visit the edges sorted by source, then sink, so the vertices and weights can be borrowed disjointly in one pass.
+        let mut order: Vec<usize> = (0..N).collect();
+        order.sort_by_key(|&i| (edges[i].0.index(), edges[i].1.index()));
+        let mut sources: Vec<usize> = order.iter().map(|&i| edges[i].0.index()).collect();
+        sources.dedup();
+
+        let mut weights = Vec::with_capacity(N);
+        for vertex in slots_mut(&mut self.guts, &sources) {
+            let vertex = vertex.as_mut()?;
+            let sinks: Vec<Id> = order
+                .iter()
+                .map(|&i| edges[i])
+                .filter(|(source, _)| *source == vertex.id())
+                .map(|(_, sink)| sink)
+                .collect();
+            weights.extend(vertex.weights_many_mut(&sinks));
+        }
+
+        let mut placed: Vec<Option<&mut E>> = (0..N).map(|_| None).collect();
+        for (i, weight) in order.into_iter().zip(weights) {
+            placed[i] = Some(weight);
         }
+        placed
+            .into_iter()
+            .collect::<Option<Vec<_>>>()?
+            .try_into()
+            .ok()
     }
 }
 
@@ -747,7 +2716,37 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
     /// This means that the [Id](struct.Id.html)s from the old graph will not work on the new one.
     #[must_use]
     pub fn recreate(&self) -> Self {
-        let mut result = Self::new();
+        self.canonicalize().0
+    }
+
+    /// Recreates a graph from scratch, like `recreate`, and returns it along with a map from each [Id](struct.Id.html) in the old graph to
+    /// the corresponding [Id](struct.Id.html) in the new one.
+    ///
+    /// The new graph has a canonical layout: its vertices are packed into indices `0..n` in the same order as they appear in `ids`,
+    /// with no empty slots. So, two graphs with the same vertices and edges (in the same relative order) have the same layout after being
+    /// canonicalized, no matter which sequence of adds and removes built them. The new [Id](struct.Id.html)s still belong to a new
+    /// generation, so they won't be equal between the two graphs.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec!["a", "removed", "b"]);
+    /// g.connect_mut(ids[0], ids[2], 2);
+    /// g.remove_mut(ids[1]);
+    ///
+    /// let (h, id_map) = g.canonicalize();
+    /// let (a, b) = (id_map[&ids[0]], id_map[&ids[2]]);
+    /// assert_eq!(h.ids().collect::<Vec<_>>(), vec![a, b]);
+    /// assert_eq!(h[(b,)], "b");
+    /// assert_eq!(h.weight(a, b), Some(&2));
+    /// assert!(!id_map.contains_key(&ids[1]));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn canonicalize(&self) -> (Self, HashMap<Id, Id>) {
+        let mut result = self.new_like();
         let mut ids = HashMap::new();
         for v in self {
             ids.insert(v.id(), result.add_mut(v.data().clone()));
@@ -755,10 +2754,64 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
 
         for source in self {
             for (sink, weight) in source {
-                result.connect_mut(ids[&source.id()], ids[&sink], weight.clone())
+                result.connect_unchecked_mut(ids[&source.id()], ids[&sink], weight.clone())
             }
         }
-        result
+        (result, ids)
+    }
+
+    /// Consumes the PGraph and returns everything in it: its vertex slots, indexed the same way as the PGraph's underlying storage
+    /// (`None` for an empty slot), and every edge as (source: Id, sink: Id, weight: E), in the same order as `edges`.
+    ///
+    /// Data and weights that aren't shared with a clone of the PGraph are moved out; shared ones are cloned.
+    /// The parts can be put back together with `add_at_mut` (to keep the same layout) and `connect_mut`, although the rebuilt
+    /// PGraph gives its vertices new [Id](struct.Id.html)s.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # use std::collections::HashMap;
+    /// # fn main() {
+    /// let mut g = PGraph::<&str, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec!["a", "removed", "b"]);
+    /// g.connect_mut(ids[0], ids[2], 2);
+    /// g.remove_mut(ids[1]);
+    ///
+    /// let (vertices, edges) = g.into_parts();
+    /// assert_eq!(vertices, vec![Some((ids[0], "a")), None, Some((ids[2], "b"))]);
+    /// assert_eq!(edges, vec![(ids[0], ids[2], 2)]);
+    ///
+    /// let mut h = PGraph::new();
+    /// let mut new_ids = HashMap::new();
+    /// for (index, slot) in vertices.into_iter().enumerate() {
+    ///     if let Some((id, data)) = slot {
+    ///         new_ids.insert(id, h.add_at_mut(index, data).unwrap());
+    ///     }
+    /// }
+    /// for (source, sink, weight) in edges {
+    ///     h.connect_mut(new_ids[&source], new_ids[&sink], weight);
+    /// }
+    /// assert_eq!(h[(new_ids[&ids[0]], new_ids[&ids[2]])], 2);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_parts(self) -> (VertexParts<V>, EdgeParts<E>) {
+        let mut edges = Vec::new();
+        let vertices = self
+            .guts
+            .into_iter()
+            .map(|slot| {
+                slot.map(|vertex| {
+                    let id = vertex.id();
+                    let (data, outbound) = vertex.into_parts();
+                    edges.extend(outbound.into_iter().map(|(sink, weight)| {
+                        (id, sink, weight.unwrap_or_else(|shared| (*shared).clone()))
+                    }));
+                    (id, data.unwrap_or_else(|shared| (*shared).clone()))
+                })
+            })
+            .collect();
+        (vertices, edges)
     }
 
     /// If there exists an outgoing edge from `source` to `sink`, returns a mutable reference to that edge's weight. Otherwise, returns `None`.
@@ -791,16 +2844,135 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
     /// ```
     #[must_use]
     pub fn weight_mut<T: Borrow<Id>>(&mut self, source: T, sink: T) -> Option<&mut E> {
-        self.vertex_mut(source).and_then(|v| v.weight_mut(sink))
+        let (vertex, _) = self.vertex_and_components_mut(*source.borrow())?;
+        vertex.weight_mut(sink)
+    }
+
+    /// Calls `f` with a mutable reference to the weight of each edge in `pairs` (given as `(source, sink)`) that
+    /// exists, skipping the ones that don't. Returns how many edges `f` was called on.
+    ///
+    /// This takes a closure instead of returning a collection of mutable references because the borrow checker
+    /// can't prove that the references would all point to different edges. (If `pairs` contains an edge more
+    /// than once, `f` is simply called on it more than once.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 21);
+    ///
+    /// let updated = g.for_each_weight_mut(vec![(id1, id2), (id2, id1), (id1, bad_id)], |_, _, w| *w *= 10);
+    /// assert_eq!(2, updated);
+    /// assert_eq!(120, g[(id1, id2)]);
+    /// assert_eq!(210, g[(id2, id1)]);
+    /// # }
+    /// ```
+    pub fn for_each_weight_mut<T, I, F>(&mut self, pairs: I, mut f: F) -> usize
+    where
+        T: Borrow<Id>,
+        I: IntoIterator<Item = (T, T)>,
+        F: FnMut(Id, Id, &mut E),
+    {
+        let mut count = 0;
+        for (source, sink) in pairs {
+            let (source, sink) = (*source.borrow(), *sink.borrow());
+            if let Some(weight) = self.weight_mut(source, sink) {
+                f(source, sink, weight);
+                count += 1;
+            }
+        }
+        count
     }
 
     /// Creates an [Edge](struct.Edge.html), which functions like HashMap's Entry, that can be used to connect `source` and `sink`
     /// if there is no existing edge, or modify the edge if there is one.
+    ///
+    /// Panics if `source` and/or `sink` is not in the PGraph. If the PGraph was created by `dag_checked` and the edge would introduce
+    /// a cycle, inserting a weight through the Edge panics.
     #[must_use]
     pub fn edge<T: Borrow<Id>>(&mut self, source: T, sink: T) -> Edge<V, E> {
+        self.index_edge(*source.borrow(), *sink.borrow());
         Edge::from(self, source, sink)
     }
 
+    /// Creates an [Edge](struct.Edge.html) for the edge from `source` to `sink`, like [`edge`](struct.PGraph.html#method.edge),
+    /// or returns `None` instead of panicking if either `source` or `sink` isn't in the PGraph. If the PGraph was created by `dag_checked`,
+    /// it also returns `None` if the edge doesn't exist and creating it would introduce a cycle.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// if let Some(e) = g.try_edge(id1, id2) {
+    ///     *e.or_default() += 12;
+    /// }
+    /// assert_eq!(g[(id1, id2)], 12);
+    ///
+    /// assert!(g.try_edge(id1, bad_id).is_none());
+    /// assert!(g.try_edge(bad_id, id2).is_none());
+    /// # }
+    /// ```
+    pub fn try_edge<T: Borrow<Id>>(&mut self, source: T, sink: T) -> Option<Edge<'_, V, E>> {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if self.has_vertex(source) && self.has_vertex(sink) {
+            self.index_edge(source, sink);
+        }
+        Edge::try_from(self, source, sink)
+    }
+
+    /// Creates an edge from `source` to `sink` whose weight is `combine(old, value)`, where `old` is the weight of the existing
+    /// edge from `source` to `sink` (or `None` if there isn't one). Panics if `source` and/or `sink` is not in the PGraph.
+    ///
+    /// This is the general form of [`connect_add_mut`](struct.PGraph.html#method.connect_add_mut), for combining repeated edges
+    /// into a single weighted edge some other way. The old weight is moved into `combine` unless a clone of the PGraph shares it,
+    /// in which case it's cloned. (Since it's moved out of the PGraph, the PGraph shouldn't be used again if `combine` panics.)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, Vec<&str>>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// let append = |old: Option<Vec<_>>, new: Vec<_>| [old.unwrap_or_default(), new].concat();
+    /// g.connect_with_mut(id1, id2, vec!["a"], append);
+    /// g.connect_with_mut(id1, id2, vec!["b"], append);
+    /// assert_eq!(g[(id1, id2)], vec!["a", "b"]);
+    /// # }
+    /// ```
+    pub fn connect_with_mut<T: Borrow<Id>, F: FnOnce(Option<E>, E) -> E>(
+        &mut self,
+        source: T,
+        sink: T,
+        value: E,
+        combine: F,
+    ) {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if let Some((vertex, _)) = self.vertex_and_components_mut(source) {
+            // The edge is put straight back, so none of the PGraph's indices have to change.
+            if let Some(old) = vertex.take_weight(sink) {
+                vertex.connect_to(sink, combine(Some(old), value));
+                return;
+            }
+        }
+        self.connect_mut(source, sink, combine(None, value));
+    }
+
     /// Removes a vertex and all edges from and to it from the PGraph.
     ///
     /// Returns the modified PGraph, which may be identical to the PGraph passed in if the vertex didn't exist.
@@ -1004,41 +3176,164 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
         }
     }
 
-    /// Removes a vertex and all edges from and to it from the PGraph.
+    /// Removes a vertex and all edges from and to it from the PGraph.
+    ///
+    /// Returns `true` if the vertex existed to be removed, `false` otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let v1 = g.add_mut(1);
+    /// let v2 = g.add_mut(2);
+    ///
+    /// let worked = g.remove_mut(bad_id);
+    /// assert!(!worked);
+    /// assert!(g.vertex(v1).is_some());
+    /// assert!(g.vertex(v2).is_some());
+    ///
+    /// let worked = g.remove_mut(v1);
+    /// assert!(worked);
+    /// assert!(g.vertex(v1).is_none());
+    /// assert!(g.vertex(v2).is_some());
+    /// # }
+    /// ```
+    pub fn remove_mut<T: Borrow<Id>>(&mut self, id: T) -> bool {
+        let id = id.borrow();
+
+        if self.has_vertex(id) {
+            self.remove_mut_no_inc(id);
+            self.idgen.next_gen();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes a vertex and all edges from and to it from the PGraph, in-place, and returns everything that was removed: the vertex's
+    /// data and the weights of its outgoing and incoming edges. (See [RemovedVertex](struct.RemovedVertex.html).)
+    /// This is useful for rewiring the edges to another vertex.
+    ///
+    /// Returns `None` (and leaves the PGraph alone) if the vertex isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id3, 23);
+    ///
+    /// let removed = g.remove_returning_mut(id2).unwrap();
+    /// assert_eq!(removed.data, 2);
+    /// assert_eq!(removed.outbound, vec![(id3, 23)]);
+    /// assert_eq!(removed.inbound, vec![(id1, 12)]);
+    /// assert!(!g.has_vertex(id2));
+    ///
+    /// for (source, weight) in removed.inbound {
+    ///     g.connect_mut(source, id3, weight);
+    /// }
+    /// assert_eq!(g[(id1, id3)], 12);
+    /// # }
+    /// ```
+    pub fn remove_returning_mut<T: Borrow<Id>>(&mut self, id: T) -> Option<RemovedVertex<V, E>> {
+        let id = *id.borrow();
+        if !self.has_vertex(id) {
+            return None;
+        }
+
+        let inbound = self
+            .predecessors(id)
+            .map(|(source, _, weight)| (source, weight.clone()))
+            .collect();
+        let removed = self.remove_mut_no_inc(id)?;
+        self.idgen.next_gen();
+
+        let outbound = removed
+            .into_iter()
+            .map(|(sink, weight)| (sink, weight.clone()))
+            .collect();
+        Some(RemovedVertex {
+            id,
+            data: removed
+                .into_data()
+                .unwrap_or_else(|shared| (*shared).clone()),
+            outbound,
+            inbound,
+        })
+    }
+
+    /// Contracts the edge from `source` to `sink`, in-place: `sink` is merged into `source`, which keeps its [Id](struct.Id.html)
+    /// and data. The edge itself is removed, every other edge to or from `sink` is moved onto `source`, and then `sink` is removed.
+    /// When a moved edge lands where `source` already has one, `combine` is called with the existing weight and the moved one.
     ///
-    /// Returns `true` if the vertex existed to be removed, `false` otherwise.
+    /// Edges from `sink` to `source` (and a self-loop on `sink`) become self-loops on `source`; use `remove_self_loops_mut`
+    /// afterwards if they aren't wanted, e.g. for Karger's algorithm.
+    ///
+    /// Returns `source`, or `None` (and leaves the PGraph alone) if there's no edge from `source` to `sink`, or if they're the same
+    /// vertex. If the PGraph was created by `dag_checked`, it also returns `None` if the contraction would close a cycle, i.e. there's
+    /// a path from `source` to `sink` besides the edge, or either of them has a self-loop. Because `sink` is removed, the PGraph moves
+    /// to a new generation.
     /// # Examples
     ///
     /// ```
     /// # use pgraph::PGraph;
     /// # fn main() {
-    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
     /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
     ///
-    /// let v1 = g.add_mut(1);
-    /// let v2 = g.add_mut(2);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[3], ids[1], 42);
     ///
-    /// let worked = g.remove_mut(bad_id);
-    /// assert!(!worked);
-    /// assert!(g.vertex(v1).is_some());
-    /// assert!(g.vertex(v2).is_some());
+    /// assert_eq!(g.contract_edge_mut(ids[0], ids[1], |old, new| *old += new), Some(ids[0]));
+    /// assert!(!g.has_vertex(ids[1]));
+    /// assert_eq!(g[(ids[0], ids[2])], 36);
+    /// assert_eq!(g[(ids[3], ids[0])], 42);
+    /// assert_eq!(g.edge_count(), 2);
     ///
-    /// let worked = g.remove_mut(v1);
-    /// assert!(worked);
-    /// assert!(g.vertex(v1).is_none());
-    /// assert!(g.vertex(v2).is_some());
+    /// assert_eq!(g.contract_edge_mut(ids[2], ids[0], |old, new| *old += new), None);
     /// # }
     /// ```
-    pub fn remove_mut<T: Borrow<Id>>(&mut self, id: T) -> bool {
-        let id = id.borrow();
+    pub fn contract_edge_mut<T: Borrow<Id>, F: FnMut(&mut E, E)>(
+        &mut self,
+        source: T,
+        sink: T,
+        mut combine: F,
+    ) -> Option<Id> {
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        if source == sink
+            || !self.has_edge(source, sink)
+            || self.contraction_closes_cycle(source, sink)
+        {
+            return None;
+        }
 
-        if self.has_vertex(id) {
-            self.remove_mut_no_inc(id);
-            self.idgen.next_gen();
-            true
-        } else {
-            false
+        let removed = self.remove_returning_mut(sink)?;
+        let mut merge =
+            |graph: &mut Self, from: Id, to: Id, weight: E| match graph.weight_mut(from, to) {
+                Some(existing) => combine(existing, weight),
+                None => graph.connect_unchecked_mut(from, to, weight),
+            };
+        for (other, weight) in removed.outbound {
+            let other = if other == sink { source } else { other };
+            merge(self, source, other, weight);
+        }
+        for (other, weight) in removed.inbound {
+            if other != source && other != sink {
+                merge(self, other, source, weight);
+            }
         }
+        Some(source)
     }
 
     /// Removes multiple vertices and all edges from and to them from the PGraph.
@@ -1074,6 +3369,114 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
         changed
     }
 
+    /// Removes multiple vertices and all edges from and to them from the PGraph, then adds a new vertex for each element of `data_iter`.
+    ///
+    /// Returns the new PGraph and the [Id](struct.Id.html)s of the added vertices, in the same order as `data_iter`.
+    /// The added vertices may reuse the removed vertices' slots, but their [Id](struct.Id.html)s are guaranteed to be distinct from the removed ones.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let old: Vec<_> = g.add_all_mut(vec![1usize, 2]);
+    ///
+    /// let (g, new): (_, Vec<_>) = g.remove_then_add(&old, vec![3usize, 4]);
+    /// assert!(old.iter().all(|id| !g.has_vertex(id) && !new.contains(id)));
+    /// assert_eq!(g[(new[0],)], 3);
+    /// assert_eq!(g[(new[1],)], 4);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn remove_then_add<
+        B: FromIterator<Id>,
+        R: Borrow<Id>,
+        IR: IntoIterator<Item = R>,
+        T: Into<V>,
+        IT: IntoIterator<Item = T>,
+    >(
+        &self,
+        remove: IR,
+        data_iter: IT,
+    ) -> (Self, B) {
+        let mut result = self.clone();
+        let ids = result.remove_then_add_mut(remove, data_iter);
+        (result, ids)
+    }
+
+    /// Removes multiple vertices and all edges from and to them from the PGraph in-place, then adds a new vertex for each element of `data_iter`.
+    ///
+    /// Returns the [Id](struct.Id.html)s of the added vertices, in the same order as `data_iter`.
+    /// The added vertices may reuse the removed vertices' slots, but their [Id](struct.Id.html)s are guaranteed to be distinct from the removed ones.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let old: Vec<_> = g.add_all_mut(vec![1usize, 2]);
+    ///
+    /// let new: Vec<_> = g.remove_then_add_mut(&old, vec![3usize, 4]);
+    /// assert!(old.iter().all(|id| !g.has_vertex(id) && !new.contains(id)));
+    /// assert_eq!(g[(new[0],)], 3);
+    /// assert_eq!(g[(new[1],)], 4);
+    /// # }
+    /// ```
+    pub fn remove_then_add_mut<
+        B: FromIterator<Id>,
+        R: Borrow<Id>,
+        IR: IntoIterator<Item = R>,
+        T: Into<V>,
+        IT: IntoIterator<Item = T>,
+    >(
+        &mut self,
+        remove: IR,
+        data_iter: IT,
+    ) -> B {
+        // `remove_all_mut` moves to a new generation whenever it frees a slot, so none of the ids created below can match a removed one.
+        self.remove_all_mut(remove);
+        self.add_all_mut(data_iter)
+    }
+
+    /// Removes multiple vertices and all edges from and to them from the PGraph, reporting which of them were actually removed.
+    ///
+    /// Returns each of the given [Id](struct.Id.html)s, in order, paired with `true` iff it was removed by this call.
+    /// (An [Id](struct.Id.html) that appears more than once is only removed the first time.)
+    /// Like `remove_all_mut`, this moves the PGraph to a new generation once at the end if anything was removed.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let v1 = g.add_mut(1);
+    /// let v2 = g.add_mut(2);
+    ///
+    /// let report = g.remove_all_reporting_mut(&[v1, bad_id, v1]);
+    /// assert_eq!(report, vec![(v1, true), (bad_id, false), (v1, false)]);
+    /// assert_eq!(g.ids().collect::<Vec<_>>(), vec![v2]);
+    /// # }
+    /// ```
+    pub fn remove_all_reporting_mut<T: Borrow<Id>, I: IntoIterator<Item = T>>(
+        &mut self,
+        ids: I,
+    ) -> Vec<(Id, bool)> {
+        let report: Vec<(Id, bool)> = ids
+            .into_iter()
+            .map(|id| {
+                let id = *id.borrow();
+                (id, self.try_remove_mut_no_inc(id))
+            })
+            .collect();
+
+        if report.iter().any(|&(_, removed)| removed) {
+            self.idgen.next_gen();
+        }
+        report
+    }
+
     /// Removes multiple vertices without incrementing the PGraph's generation.
     ///
     /// Not public because while it isn't `unsafe`, calling it wouthout calling
@@ -1108,20 +3511,6 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
         }
     }
 
-    /// Removes a vertex without incrementing the PGraph's generation.
-    ///
-    /// Not public because while it isn't `unsafe`, calling it wouthout calling
-    /// `self.idgen.next_gen()` afterwards potentially causes use-after-free problems.
-    /// (If the removed vertex was in the most recent generation, then adding a new
-    /// vertex in its slot will have the same [Id](struct.Id.html) as the old vertex.)
-    fn remove_mut_no_inc<T: Borrow<Id>>(&mut self, id: T) {
-        let id = id.borrow();
-        let index = id.index();
-        self.guts.set(index, None);
-        self.empties.insert(index);
-        self.disconnect_all_inc_mut(id);
-    }
-
     /// Removes the edge from `source` to `sink`, if one exists. Panics if `source` doesn't exist.
     ///
     /// Returns `true` if there was previously an edge from `source` to `sink`
@@ -1148,7 +3537,13 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
     /// # }
     /// ```
     pub fn disconnect_mut<T: Borrow<Id>>(&mut self, source: T, sink: T) -> bool {
-        self[source].disconnect(sink)
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        let removed = self.vertex_keeping_components_mut(source).disconnect(sink);
+        if removed {
+            self.unindex_edge(source, sink);
+            self.invalidate_components();
+        }
+        removed
     }
 
     /// Tries to remove the edge from `source` to `sink`, if one exists.
@@ -1182,18 +3577,289 @@ impl<V: Clone, E: Clone> PGraph<V, E> {
     /// # }
     /// ```
     pub fn try_disconnect_mut<T: Borrow<Id>>(&mut self, source: T, sink: T) -> bool {
-        self.vertex_mut(source)
-            .map_or(false, |v| v.disconnect(sink))
+        let (source, sink) = (*source.borrow(), *sink.borrow());
+        let removed = self
+            .vertex_and_components_mut(source)
+            .map_or(false, |(v, _)| v.disconnect(sink));
+        if removed {
+            self.unindex_edge(source, sink);
+            self.invalidate_components();
+        }
+        removed
     }
 
-    /// Disconnects all the edges that end at `sink`.
-    fn disconnect_all_inc_mut<T: Borrow<Id>>(&mut self, sink: T) -> bool {
-        let sink = sink.borrow();
+    /// Removes every isolated vertex (one with no outgoing and no incoming edges) from the PGraph, in-place.
+    ///
+    /// Returns the number of vertices removed.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    ///
+    /// assert_eq!(2, g.remove_isolated_mut());
+    /// assert_eq!(0, g.remove_isolated_mut());
+    /// assert_eq!(g.ids().collect::<Vec<_>>(), vec![ids[0], ids[1]]);
+    /// # }
+    /// ```
+    pub fn remove_isolated_mut(&mut self) -> usize {
+        let isolated: Vec<Id> = self.isolated_vertices().collect();
+        self.remove_all_mut(&isolated);
+        isolated.len()
+    }
 
-        let inc: Vec<Id> = self.predecessor_ids(sink).collect();
-        inc.into_iter().fold(false, |init, source| {
-            self.disconnect_mut(source, *sink) || init
-        })
+    /// Removes every self-loop (an edge from a vertex to itself) from the PGraph, in-place.
+    ///
+    /// Returns the number of self-loops removed.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id1, 11);
+    /// g.connect_mut(id2, id2, 22);
+    /// g.connect_mut(id2, id1, 21);
+    ///
+    /// assert_eq!(2, g.remove_self_loops_mut());
+    /// assert_eq!(0, g.remove_self_loops_mut());
+    /// assert!(g.has_edge(id2, id1));
+    /// # }
+    /// ```
+    pub fn remove_self_loops_mut(&mut self) -> usize {
+        let looped: Vec<Id> = self.self_loops().map(|(id, _)| id).collect();
+        for id in &looped {
+            self.disconnect_mut(id, id);
+        }
+        looped.len()
+    }
+
+    /// Removes every edge that _starts_ at `source` for which `pred` returns `true`, in-place. `pred` is called once for each
+    /// of `source`'s edges, with the edge's sink and weight.
+    ///
+    /// Returns the number of edges removed, which is 0 if `source` isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id1, 11);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    ///
+    /// assert_eq!(2, g.disconnect_where_mut(id1, |sink, weight| sink == id1 || *weight > 12));
+    /// assert!(g.has_edge(id1, id2));
+    /// assert_eq!(g.edge_count(), 1);
+    /// # }
+    /// ```
+    pub fn disconnect_where_mut<T: Borrow<Id>, F: FnMut(Id, &E) -> bool>(
+        &mut self,
+        source: T,
+        mut pred: F,
+    ) -> usize {
+        let source = *source.borrow();
+        let track_sinks = self.has_reverse_index();
+        let mut sinks = Vec::new();
+
+        let removed = match self.vertex_and_components_mut(source) {
+            Some((v, _)) => v.disconnect_where(|sink, weight| {
+                let remove = pred(sink, weight);
+                if remove && track_sinks {
+                    sinks.push(sink);
+                }
+                remove
+            }),
+            None => 0,
+        };
+
+        for sink in sinks {
+            self.unindex_edge(source, sink);
+        }
+        if removed > 0 {
+            self.invalidate_components();
+        }
+        removed
+    }
+
+    /// Removes every edge that _ends_ at `sink`, in-place, without removing `sink` itself. Its outgoing edges are left alone,
+    /// except for a self-loop, which is removed.
+    ///
+    /// Returns the number of edges removed, which is 0 if `sink` isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[1], ids[2], 23);
+    /// g.connect_mut(ids[2], ids[0], 31);
+    ///
+    /// assert_eq!(g.disconnect_all_to_mut(ids[2]), 2);
+    /// assert_eq!(g.in_degree(ids[2]), 0);
+    /// assert!(g.has_edge(ids[2], ids[0]));
+    /// assert_eq!(g.disconnect_all_to_mut(ids[2]), 0);
+    /// # }
+    /// ```
+    pub fn disconnect_all_to_mut<T: Borrow<Id>>(&mut self, sink: T) -> usize {
+        let sink = *sink.borrow();
+        if !self.has_vertex(sink) {
+            return 0;
+        }
+
+        let removed = self.disconnect_all_inc_mut(sink);
+        if removed > 0 {
+            self.invalidate_components();
+        }
+        removed
+    }
+
+    /// Creates an edge with the given weight from each vertex in `path` to the next one, in-place. Existing edges along the path are
+    /// overwritten. (A path that visits the same vertex twice in a row creates a self-loop on it.)
+    ///
+    /// Returns `false` iff some vertex in `path` isn't in the PGraph, or the PGraph was created by `dag_checked` and the path would
+    /// introduce a cycle. In either case, the whole path is rejected and no edges are created, not even the ones before the problem.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// assert!(g.connect_path_mut(vec![ids[0], ids[1], ids[2]], 1));
+    /// assert_eq!(g.edge_count(), 2);
+    /// assert_eq!(g[(ids[1], ids[2])], 1);
+    ///
+    /// assert!(!g.connect_path_mut(vec![ids[2], ids[0], bad_id], 2));
+    /// assert!(!g.has_edge(ids[2], ids[0]));
+    /// # }
+    /// ```
+    pub fn connect_path_mut<I: IntoIterator<Item = Id>>(&mut self, path: I, weight: E) -> bool {
+        let path: Vec<Id> = path.into_iter().collect();
+        if !self.all_present(&path) || self.path_closes_cycle(&path) {
+            return false;
+        }
+
+        for pair in path.windows(2) {
+            self.connect_mut(pair[0], pair[1], weight.clone());
+        }
+        true
+    }
+}
+
+impl<V: Clone, E: Clone + PartialEq> PGraph<V, E> {
+    /// Creates an edge from `source` to `sink` with the given weight, or sets the weight of the existing edge, in-place.
+    /// Reports whether the edge was created, left alone because it already had an equal weight, or updated.
+    /// (Use `insert_edge_mut` if `E` doesn't implement `PartialEq`.)
+    ///
+    /// Panics if `source` and/or `sink` is not in the PGraph
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::{EdgeUpsert, PGraph};
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// assert_eq!(g.upsert_edge_mut(id1, id2, 12), EdgeUpsert::Created);
+    /// assert_eq!(g.upsert_edge_mut(id1, id2, 12), EdgeUpsert::Unchanged);
+    /// assert_eq!(g.upsert_edge_mut(id1, id2, 21), EdgeUpsert::Updated(12));
+    /// assert_eq!(g[(id1, id2)], 21);
+    /// # }
+    /// ```
+    pub fn upsert_edge_mut<T: Borrow<Id>>(
+        &mut self,
+        source: T,
+        sink: T,
+        weight: E,
+    ) -> EdgeUpsert<E> {
+        let (source, sink) = (source.borrow(), sink.borrow());
+        match self.weight(source, sink) {
+            Some(old) if *old == weight => EdgeUpsert::Unchanged,
+            Some(_) => match self.weight_mut(source, sink) {
+                Some(old) => EdgeUpsert::Updated(mem::replace(old, weight)),
+                None => unreachable!("The edge existed a moment ago."),
+            },
+            None => {
+                self.connect_mut(source, sink, weight);
+                EdgeUpsert::Created
+            }
+        }
+    }
+}
+
+impl<V: Clone, E: Clone + Default> PGraph<V, E> {
+    /// Returns a mutable reference to the weight of the edge from `source` to `sink`, creating that edge with a weight of
+    /// `E::default()` first if it doesn't exist.
+    ///
+    /// Returns `None` (without creating anything) if `source` and/or `sink` is not in the PGraph, or if the PGraph was created by
+    /// `dag_checked` and the edge would introduce a cycle. Unlike `edge(source, sink).or_default()`, this never panics, so it's safe to use
+    /// with untrusted [Id](struct.Id.html)s.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// # let (_, bad_id) = PGraph::<usize, usize>::new().add(0);
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// *g.weight_or_default_mut(id1, id2).unwrap() += 5;
+    /// *g.weight_or_default_mut(id1, id2).unwrap() += 7;
+    /// assert_eq!(g.weight(id1, id2), Some(&12));
+    ///
+    /// assert!(g.weight_or_default_mut(id1, bad_id).is_none());
+    /// assert!(g.weight_or_default_mut(bad_id, id2).is_none());
+    /// assert_eq!(g.edge_count(), 1);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn weight_or_default_mut<T: Borrow<Id>>(&mut self, source: T, sink: T) -> Option<&mut E> {
+        self.try_edge(source, sink).map(Edge::or_default)
+    }
+}
+
+impl<V: Clone, E: Add<Output = E> + Clone + Default> PGraph<V, E> {
+    /// Adds `delta` to the weight of the edge from `source` to `sink`, creating the edge with a weight of `delta` if it doesn't exist.
+    /// Panics if `source` and/or `sink` is not in the PGraph.
+    ///
+    /// Useful for summing repeated edges into a single weighted edge, e.g. when building a PGraph from a multigraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    ///
+    /// g.connect_add_mut(id1, id2, 5);
+    /// g.connect_add_mut(id1, id2, 7);
+    /// assert_eq!(g[(id1, id2)], 12);
+    /// # }
+    /// ```
+    pub fn connect_add_mut<T: Borrow<Id>>(&mut self, source: T, sink: T, delta: E) {
+        self.connect_with_mut(source, sink, delta, |old, delta| {
+            old.unwrap_or_default() + delta
+        });
     }
 }
 
@@ -1202,26 +3868,15 @@ impl<V, E, T: Borrow<Id>> Index<T> for PGraph<V, E> {
 
     fn index(&self, id: T) -> &Vertex<V, E> {
         let id = id.borrow();
-
-        match self.guts.get(id.index()) {
-            Some(Some(vertex)) if vertex.same_id(id) => vertex,
-            Some(Some(_)) => panic!("The Id {:?} is of an invalid generation. It does not correspond to any vertices in this graph.", id),
-            Some(None) => panic!("No vertex found for Id {:?}. It has likely been removed from the graph.", id),
-            None => panic!("No vertex found for Id {:?}. The Id either comes from a chlid or another graph family.", id),
-        }
+        self.get_vertex(id)
+            .unwrap_or_else(|| self.missing_vertex(*id))
     }
 }
 
 impl<V: Clone, E, T: Borrow<Id>> IndexMut<T> for PGraph<V, E> {
     fn index_mut(&mut self, id: T) -> &mut Vertex<V, E> {
-        let id = id.borrow();
-
-        match self.guts.get_mut(id.index()) {
-            Some(Some(vertex)) if vertex.same_id(id) => vertex,
-            Some(Some(_)) => panic!("The Id {:?} is of an invalid generation. It does not correspond to any vertices in this graph", id),
-            Some(None) => panic!("No vertex found for Id {:?}. It has likely been removed from the graph.", id),
-            None => panic!("No vertex found for Id {:?}. The Id either comes from a chlid or another graph family.", id),
-        }
+        self.invalidate_components();
+        self.vertex_keeping_components_mut(*id.borrow())
     }
 }
 
@@ -1235,7 +3890,8 @@ impl<V, E, T: Borrow<Id>> Index<(T,)> for PGraph<V, E> {
 
 impl<V: Clone, E, T: Borrow<Id>> IndexMut<(T,)> for PGraph<V, E> {
     fn index_mut(&mut self, id: (T,)) -> &mut V {
-        self[id.0].data_mut()
+        self.vertex_keeping_components_mut(*id.0.borrow())
+            .data_mut()
     }
 }
 
@@ -1243,15 +3899,20 @@ impl<V, E, T: Borrow<Id>> Index<(T, T)> for PGraph<V, E> {
     type Output = E;
 
     fn index(&self, ids: (T, T)) -> &E {
-        let (source, sink) = ids;
-        self[source].index(sink)
+        let (source, sink) = (*ids.0.borrow(), *ids.1.borrow());
+        match self.get_edge_indexed(source, sink) {
+            Some(weight) => weight,
+            None if !self.has_vertex(source) => self.missing_vertex(source),
+            None => panic!("There is no edge from {:?} to {:?}.", source, sink),
+        }
     }
 }
 
 impl<V: Clone, E: Clone, T: Borrow<Id>> IndexMut<(T, T)> for PGraph<V, E> {
     fn index_mut(&mut self, ids: (T, T)) -> &mut E {
         let (source, sink) = ids;
-        self[source].index_mut(sink)
+        self.vertex_keeping_components_mut(*source.borrow())
+            .index_mut(sink)
     }
 }
 
@@ -1276,6 +3937,23 @@ fn remove_all<'a, V: Clone, E: Clone, T: Borrow<Id>, I: IntoIterator<Item = T>>(
         .fold(false, |changed, into_id| remove(cow, into_id) || changed)
 }
 
+/// Gets mutable references to the elements of `vector` at each of `indices`, which have to be strictly increasing and in bounds.
+///
+/// The references are split off of one mutable focus, so they're guaranteed not to alias.
+fn slots_mut<'a, A: Clone>(vector: &'a mut Vector<A>, indices: &[usize]) -> Vec<&'a mut A> {
+    let mut slots = Vec::with_capacity(indices.len());
+    let mut rest = vector.focus_mut();
+    let mut offset = 0;
+    for &index in indices {
+        let (_, tail) = rest.split_at(index - offset);
+        let (slot, tail) = tail.split_at(1);
+        slots.extend(slot);
+        rest = tail;
+        offset = index + 1;
+    }
+    slots
+}
+
 type GutsIter<'a, V, E> = <&'a GraphInternal<V, E> as IntoIterator>::IntoIter;
 type VertexDeref<'a, V, E> = fn(&'a Option<Vertex<V, E>>) -> Option<&'a Vertex<V, E>>;
 type VertexIter<'a, V, E> = FilterMap<GutsIter<'a, V, E>, VertexDeref<'a, V, E>>;
@@ -0,0 +1,37 @@
+//! Parallel iterators over a `PGraph`, using `rayon`. Only available with the `rayon` feature.
+
+use super::{Id, NodeEdgeIter, PGraph, Vertex};
+use rayon::prelude::*;
+
+impl<V: Send + Sync, E: Send + Sync> PGraph<V, E> {
+    /// Returns a parallel iterator over every edge in the PGraph, visiting each one exactly once. Like `edges`, the iterator's items
+    /// are (source: Id, sink: Id, edge: &E), but they aren't yielded in any particular order.
+    ///
+    /// The work is split up by source vertex, so a PGraph whose edges mostly start at a handful of vertices won't parallelize well.
+    /// Collecting the vertices to split up takes O(V) time before any edges are visited.
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rayon;
+    /// # use pgraph::PGraph;
+    /// # use rayon::prelude::*;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id3, id2, 32);
+    ///
+    /// let total: usize = g.par_edges().map(|(_, _, weight)| weight).sum();
+    /// assert_eq!(total, 57);
+    /// # }
+    /// ```
+    pub fn par_edges(&self) -> impl ParallelIterator<Item = (Id, Id, &E)> {
+        let vertices: Vec<&Vertex<V, E>> = self.into_iter().collect();
+        vertices.into_par_iter().flat_map_iter(NodeEdgeIter::from)
+    }
+}
@@ -0,0 +1,100 @@
+//! Random sampling over a `PGraph`'s edges, using `rand`. Only available with the `rand` feature.
+
+use super::{Id, PGraph};
+use rand::Rng;
+use std::borrow::Borrow;
+
+impl<V, E: Into<f64> + Clone> PGraph<V, E> {
+    /// Picks one of the vertices that `source` has an edge to, with a probability proportional to the weight of that edge.
+    ///
+    /// Weights are converted to `f64` with `Into`; an edge whose weight isn't positive is never picked. Returns `None` if `source`
+    /// isn't in the PGraph or has no edge with a positive weight.
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # use pgraph::PGraph;
+    /// # use rand::rngs::StdRng;
+    /// # use rand::SeedableRng;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, u32>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[1], 1);
+    /// g.connect_mut(ids[0], ids[2], 0);
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// assert_eq!(g.sample_neighbor(ids[0], &mut rng), Some(ids[1]));
+    /// assert_eq!(g.sample_neighbor(ids[1], &mut rng), None);
+    /// # }
+    /// ```
+    pub fn sample_neighbor<T: Borrow<Id>, R: Rng>(&self, source: T, rng: &mut R) -> Option<Id> {
+        let candidates: Vec<(Id, f64)> = self
+            .outbound_edges(source)
+            .map(|(_, sink, weight)| (sink, weight.clone().into()))
+            .filter(|&(_, weight)| weight > 0.0)
+            .collect();
+        let total: f64 = candidates.iter().map(|&(_, weight)| weight).sum();
+        let (&(last, _), rest) = candidates.split_last()?;
+
+        let mut target = rng.gen::<f64>() * total;
+        for &(sink, weight) in rest {
+            if target < weight {
+                return Some(sink);
+            }
+            target -= weight;
+        }
+        // Rounding can leave `target` a little above the last weight, so the last edge takes whatever is left.
+        Some(last)
+    }
+
+    /// Takes a random walk of up to `steps` steps from `start`, choosing each step with `sample_neighbor`. Returns the vertices
+    /// visited, starting with `start` itself.
+    ///
+    /// The walk stops early if it reaches a vertex with no edge to step along, so the result has at most `steps + 1` vertices.
+    /// It's empty if `start` isn't in the PGraph.
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # use pgraph::PGraph;
+    /// # use rand::rngs::StdRng;
+    /// # use rand::SeedableRng;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, f64>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[1], 0.5);
+    /// g.connect_mut(ids[1], ids[0], 0.5);
+    /// g.connect_mut(ids[1], ids[2], 0.5);
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let walk = g.random_walk(ids[0], 10, &mut rng);
+    /// assert_eq!(walk[0], ids[0]);
+    /// assert!(walk.len() <= 11);
+    /// assert!(walk.windows(2).all(|step| g.has_edge(step[0], step[1])));
+    /// # }
+    /// ```
+    pub fn random_walk<T: Borrow<Id>, R: Rng>(
+        &self,
+        start: T,
+        steps: usize,
+        rng: &mut R,
+    ) -> Vec<Id> {
+        let start = *start.borrow();
+        if !self.has_vertex(start) {
+            return Vec::new();
+        }
+
+        let mut walk = vec![start];
+        let mut current = start;
+        for _ in 0..steps {
+            match self.sample_neighbor(current, rng) {
+                Some(next) => {
+                    walk.push(next);
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        walk
+    }
+}
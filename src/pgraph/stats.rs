@@ -0,0 +1,499 @@
+//! Summary statistics describing the shape of a `PGraph`.
+
+use super::{adj, Id, PGraph, Vertex};
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::mem::size_of;
+use std::ops::Add;
+
+/// A rough picture of how much storage a PGraph is using, as returned by
+/// [`structural_stats`](struct.PGraph.html#method.structural_stats).
+///
+/// The counts are exact, but `approx_bytes` is only an estimate. (See `structural_stats`.)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StructuralStats {
+    /// The number of vertices in the PGraph.
+    pub vertices: usize,
+    /// The number of slots in the PGraph's underlying storage left empty by removed vertices.
+    pub empty_slots: usize,
+    /// The total number of slots (empty or not) in every vertex's adjacency list.
+    pub adjacency_slots: usize,
+    /// An estimate of the number of bytes the PGraph's vertices, edges, data and weights take up.
+    pub approx_bytes: usize,
+}
+
+impl<V, E> PGraph<V, E> {
+    /// Returns the total degree (number of outgoing edges plus number of incoming edges) of every vertex in the
+    /// PGraph, sorted from highest to lowest. A self-loop counts towards both its vertex's in- and out-degree.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id3, id3, 33);
+    ///
+    /// assert_eq!(g.degree_sequence(), vec![3, 2, 1]);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.guts.len()];
+        for (source, sink, _) in self.edges() {
+            degrees[source.index()] += 1;
+            degrees[sink.index()] += 1;
+        }
+
+        let mut result: Vec<_> = self.ids().map(|id| degrees[id.index()]).collect();
+        result.sort_unstable_by(|a, b| b.cmp(a));
+        result
+    }
+
+    /// Returns the density of the PGraph: the number of edges divided by the `n * (n - 1)` edges a directed graph
+    /// with `n` vertices can have between distinct vertices.
+    ///
+    /// Graphs with zero or one vertices have a density of `0.0` (rather than `NaN`). Since self-loops aren't counted
+    /// in the maximum, a graph with self-loops can have a density greater than `1.0`.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// assert_eq!(g.density(), 0.0);
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// assert_eq!(g.density(), 0.0);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// assert_eq!(g.density(), 0.5);
+    ///
+    /// g.connect_mut(id2, id1, 21);
+    /// assert_eq!(g.density(), 1.0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn density(&self) -> f64 {
+        let n = self.count_vertices();
+        if n <= 1 {
+            0.0
+        } else {
+            self.edge_count() as f64 / (n * (n - 1)) as f64
+        }
+    }
+
+    /// Counts the empty slots left behind in the PGraph's underlying storage by removed vertices that haven't been reused yet.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.remove_all_mut(&ids[..2]);
+    /// assert_eq!(g.empty_slots(), 2);
+    ///
+    /// g.add_mut(4);
+    /// assert_eq!(g.empty_slots(), 1);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn empty_slots(&self) -> usize {
+        self.empties.len()
+    }
+
+    /// Returns the sizes of the PGraph's underlying storage, along with an estimate of how many bytes it takes up. This is meant
+    /// to help decide when it's worth calling `shrink_to_fit_mut` or recreating the PGraph.
+    ///
+    /// The byte count is only a ballpark figure. It's worked out from the number of slots in each `im::Vector` and the sizes of
+    /// their elements, so it leaves out the overhead of the vectors' trees and any spare capacity in their chunks. It also
+    /// counts every vertex's data and every edge's weight as if this PGraph were the only one holding them; if it shares
+    /// structure with its clones, the memory actually freed by dropping it will be smaller. Data and weights that own heap
+    /// memory of their own (e.g. `String`s) only count for their inline size.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.remove_mut(ids[1]);
+    ///
+    /// let stats = g.structural_stats();
+    /// assert_eq!(stats.vertices, 2);
+    /// assert_eq!(stats.empty_slots, 1);
+    /// assert_eq!(stats.adjacency_slots, 3);
+    /// assert!(stats.approx_bytes > 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn structural_stats(&self) -> StructuralStats {
+        let adjacency_slots = self.into_iter().map(Vertex::neighbor_slots).sum();
+        let arc_overhead = 2 * size_of::<usize>();
+
+        StructuralStats {
+            vertices: self.count_vertices(),
+            empty_slots: self.empty_slots(),
+            adjacency_slots,
+            approx_bytes: self.guts.len() * size_of::<Option<Vertex<V, E>>>()
+                + self.count_vertices() * (arc_overhead + size_of::<V>())
+                + adjacency_slots * size_of::<Option<adj::Edge<E>>>()
+                + self.edge_count() * (arc_overhead + size_of::<E>())
+                + self.reverse.as_ref().map_or(0, |reverse| {
+                    reverse.iter().map(|sources| sources.len()).sum::<usize>() * size_of::<Id>()
+                }),
+        }
+    }
+
+    /// Returns the fraction of the slots in the PGraph's underlying storage that hold a vertex, from `0.0` to `1.0`.
+    /// A low load factor means the PGraph is fragmented, and might be worth shrinking or recreating.
+    ///
+    /// A PGraph with no slots at all has a load factor of `1.0` (rather than `NaN`), since none of its space is wasted.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// assert_eq!(g.load_factor(), 1.0);
+    ///
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    /// g.remove_mut(ids[0]);
+    /// assert_eq!(g.load_factor(), 0.75);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn load_factor(&self) -> f64 {
+        let slots = self.guts.len();
+        if slots == 0 {
+            1.0
+        } else {
+            self.count_vertices() as f64 / slots as f64
+        }
+    }
+
+    /// Buckets every edge in the PGraph by the result of calling `key` on its weight, and counts the edges in each bucket.
+    /// Buckets with no edges don't appear in the result.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, f64>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    ///
+    /// g.connect_mut(id1, id2, 0.2);
+    /// g.connect_mut(id1, id3, 1.7);
+    /// g.connect_mut(id3, id3, 2.4);
+    ///
+    /// let histogram = g.weight_histogram(|weight| weight.round() as i64);
+    /// assert_eq!(histogram.len(), 2);
+    /// assert_eq!(histogram[&0], 1);
+    /// assert_eq!(histogram[&2], 2);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn weight_histogram<K: Eq + Hash, F: FnMut(&E) -> K>(
+        &self,
+        mut key: F,
+    ) -> HashMap<K, usize> {
+        let mut histogram = HashMap::new();
+        for weight in self.iter_weights() {
+            *histogram.entry(key(weight)).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Returns the `k` vertices with the highest scores, as (vertex: Id, score: S), from highest score to lowest.
+    /// `score` is called once for each vertex in the PGraph. If the PGraph has fewer than `k` vertices, all of them are returned.
+    ///
+    /// Ties are broken arbitrarily, both when deciding which vertices make the cut and in the order of the result.
+    /// This keeps a heap of at most `k` vertices, so it takes O(V log k) time rather than sorting every vertex.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![5usize, 1, 4, 2]);
+    ///
+    /// let top = g.top_k_vertices(2, |_, data| *data);
+    /// assert_eq!(top, vec![(ids[0], 5), (ids[2], 4)]);
+    ///
+    /// assert_eq!(g.top_k_vertices(10, |_, data| *data).len(), 4);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn top_k_vertices<S: Ord, F: FnMut(Id, &V) -> S>(
+        &self,
+        k: usize,
+        mut score: F,
+    ) -> Vec<(Id, S)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        // A min-heap of the best `k` seen so far, so the worst of them is the one to evict.
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for v in self {
+            heap.push(Reverse((score(v.id(), v.data()), v.id())));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((score, id))| (id, score))
+            .collect()
+    }
+}
+
+impl<V, E: Add<Output = E> + Default + Clone> PGraph<V, E> {
+    /// Returns the sum of the weights of every edge in the PGraph, or `E::default()` if the PGraph has no edges.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, f64>::new();
+    /// assert_eq!(g.total_weight(), 0.0);
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 1.5);
+    /// g.connect_mut(id2, id1, 2.0);
+    /// g.connect_mut(id2, id2, 0.25);
+    ///
+    /// assert_eq!(g.total_weight(), 3.75);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn total_weight(&self) -> E {
+        self.iter_weights()
+            .fold(E::default(), |total, weight| total + weight.clone())
+    }
+
+    /// Returns the sum of the weights of the edges that _start_ at `id`, or `E::default()` if there are none or `id` isn't in the PGraph.
+    /// A self-loop on `id` is included in both its weighted out-degree and its weighted in-degree.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id1, 11);
+    ///
+    /// assert_eq!(g.weighted_out_degree(id1), 23);
+    /// assert_eq!(g.weighted_out_degree(id2), 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn weighted_out_degree<T: Borrow<Id>>(&self, id: T) -> E {
+        self.outbound_edges(id)
+            .fold(E::default(), |total, (_, _, weight)| total + weight.clone())
+    }
+
+    /// Returns the sum of the weights of the edges that _end_ at `id`, or `E::default()` if there are none or `id` isn't in the PGraph.
+    /// A self-loop on `id` is included in both its weighted out-degree and its weighted in-degree.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id1, 11);
+    ///
+    /// assert_eq!(g.weighted_in_degree(id1), 11);
+    /// assert_eq!(g.weighted_in_degree(id2), 12);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn weighted_in_degree<T: Borrow<Id>>(&self, id: T) -> E {
+        self.predecessors(id)
+            .fold(E::default(), |total, (_, _, weight)| total + weight.clone())
+    }
+}
+
+impl<V, E: PartialEq> PGraph<V, E> {
+    /// Returns `true` iff every edge `a -> b` in the PGraph has a matching edge `b -> a` with an equal weight,
+    /// i.e. iff the PGraph is a faithful encoding of an undirected graph. Self-loops are always symmetric.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 3);
+    /// g.connect_mut(id1, id1, 2);
+    /// assert!(!g.is_symmetric());
+    ///
+    /// g.connect_mut(id2, id1, 3);
+    /// assert!(g.is_symmetric());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_symmetric(&self) -> bool {
+        self.asymmetric_edges().next().is_none()
+    }
+
+    /// Returns an iterator over every edge `a -> b` in the PGraph that doesn't have a matching edge `b -> a` with an equal weight.
+    /// The iterator's items are (source: Id, sink: Id)
+    ///
+    /// If the edges between two vertices go both ways but have different weights, both of them are included.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id2, 3);
+    /// g.connect_mut(id2, id1, 3);
+    /// g.connect_mut(id2, id3, 5);
+    /// g.connect_mut(id3, id2, 6);
+    /// g.connect_mut(id3, id1, 4);
+    ///
+    /// let asymmetric: Vec<_> = g.asymmetric_edges().collect();
+    /// assert_eq!(asymmetric, vec![(id2, id3), (id3, id1), (id3, id2)]);
+    /// # }
+    /// ```
+    pub fn asymmetric_edges(&self) -> impl Iterator<Item = (Id, Id)> + '_ {
+        self.edges()
+            .filter(move |(source, sink, weight)| self.weight(sink, source) != Some(weight))
+            .map(|(source, sink, _)| (source, sink))
+    }
+}
+
+impl<V, E: Ord> PGraph<V, E> {
+    /// Returns the edge with the smallest weight in the PGraph, as (source: Id, sink: Id, edge: &E), or `None` if there are no edges.
+    /// If several edges share the smallest weight, returns the first of them in the order `edges` yields them.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// assert_eq!(g.min_edge(), None);
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 3);
+    /// g.connect_mut(id2, id2, 3);
+    ///
+    /// assert_eq!(g.min_edge(), Some((id2, id1, &3)));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn min_edge(&self) -> Option<(Id, Id, &E)> {
+        self.edges().min_by(|(_, _, a), (_, _, b)| a.cmp(b))
+    }
+
+    /// Returns the edge with the largest weight in the PGraph, as (source: Id, sink: Id, edge: &E), or `None` if there are no edges.
+    /// If several edges share the largest weight, returns the first of them in the order `edges` yields them.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// assert_eq!(g.max_edge(), None);
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 12);
+    /// g.connect_mut(id2, id2, 3);
+    ///
+    /// assert_eq!(g.max_edge(), Some((id1, id2, &12)));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_edge(&self) -> Option<(Id, Id, &E)> {
+        // `max_by` would return the _last_ of several equal maximums.
+        self.edges().min_by(|(_, _, a), (_, _, b)| b.cmp(a))
+    }
+
+    /// Returns the edge with the smallest weight out of the ones that _start_ at `source`, or `None` if there are none or `source` isn't in
+    /// the PGraph. If several edges share the smallest weight, returns the one with the lowest sink index.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id3, id1, 1);
+    ///
+    /// assert_eq!(g.min_out_edge(id1), Some((id1, id2, &12)));
+    /// assert_eq!(g.min_out_edge(id2), None);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn min_out_edge<T: Borrow<Id>>(&self, source: T) -> Option<(Id, Id, &E)> {
+        self.outbound_edges(source)
+            .min_by(|(_, _, a), (_, _, b)| a.cmp(b))
+    }
+
+    /// Returns the edge with the largest weight out of the ones that _start_ at `source`, or `None` if there are none or `source` isn't in
+    /// the PGraph. If several edges share the largest weight, returns the one with the lowest sink index.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id1, id3, 13);
+    /// g.connect_mut(id3, id1, 100);
+    ///
+    /// assert_eq!(g.max_out_edge(id1), Some((id1, id3, &13)));
+    /// assert_eq!(g.max_out_edge(id2), None);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_out_edge<T: Borrow<Id>>(&self, source: T) -> Option<(Id, Id, &E)> {
+        self.outbound_edges(source)
+            .min_by(|(_, _, a), (_, _, b)| b.cmp(a))
+    }
+}
@@ -0,0 +1,226 @@
+//! A view of a `PGraph` that treats its edges as undirected, so it can be used with `petgraph`'s undirected algorithms.
+
+use super::{EdgeIter, Id, OutboundIter, PGraph, PredecessorIter};
+use petgraph::data::DataMap;
+use petgraph::visit::{
+    Data, GraphBase, GraphProp, GraphRef, IntoEdgeReferences, IntoEdges, IntoNeighbors,
+    IntoNodeIdentifiers, NodeCompactIndexable, NodeCount, NodeIndexable, Visitable,
+};
+use std::collections::HashSet;
+use std::iter::Map;
+
+/// A read-only view of a [PGraph](struct.PGraph.html) in which every edge is treated as undirected. Created by
+/// [`as_undirected`](struct.PGraph.html#method.as_undirected).
+///
+/// The view doesn't copy anything. Two vertices connected in both directions count as a single undirected edge, which
+/// keeps the weight of the edge from the vertex with the lower index (or the only weight, if they're only connected one way).
+/// The edges yielded by `IntoEdges` always start at the vertex they were asked for, even if the underlying edge ends there.
+pub struct Undirected<'a, V, E> {
+    graph: &'a PGraph<V, E>,
+}
+
+// `derive(Clone, Copy)` only implements for <V: Clone, E: Clone> because of rust#26925
+impl<'a, V, E> Clone for Undirected<'a, V, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V, E> Copy for Undirected<'a, V, E> {}
+
+impl<V, E> PGraph<V, E> {
+    /// Creates a view of this PGraph in which every edge is undirected, for use with `petgraph`'s undirected algorithms.
+    /// (See [Undirected](struct.Undirected.html).)
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate petgraph;
+    /// # use pgraph::PGraph;
+    /// # use petgraph::algo::connected_components;
+    /// # use petgraph::visit::IntoNeighbors;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    ///
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// let id3 = g.add_mut(3);
+    /// g.add_mut(4);
+    ///
+    /// g.connect_mut(id1, id2, 12);
+    /// g.connect_mut(id2, id1, 21);
+    /// g.connect_mut(id3, id2, 32);
+    ///
+    /// let u = g.as_undirected();
+    /// let neighbors: Vec<_> = u.neighbors(id2).collect();
+    /// assert_eq!(neighbors, vec![id1, id3]);
+    /// assert_eq!(connected_components(u), 2);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn as_undirected(&self) -> Undirected<'_, V, E> {
+        Undirected { graph: self }
+    }
+}
+
+impl<'a, V, E> Undirected<'a, V, E> {
+    /// Returns the PGraph this view is of.
+    #[must_use]
+    pub fn graph(&self) -> &'a PGraph<V, E> {
+        self.graph
+    }
+
+    /// Returns `true` iff this edge is the one that represents its pair of vertices in the view, i.e. it's the only edge
+    /// between them, or it starts at the vertex with the lower index.
+    fn is_representative(&self, source: Id, sink: Id) -> bool {
+        source.index() <= sink.index() || !self.graph.has_edge(sink, source)
+    }
+}
+
+impl<'a, V, E> GraphBase for Undirected<'a, V, E> {
+    type NodeId = Id;
+    type EdgeId = (Id, Id);
+}
+
+impl<'a, V, E> GraphRef for Undirected<'a, V, E> {}
+
+impl<'a, V, E> GraphProp for Undirected<'a, V, E> {
+    type EdgeType = petgraph::Undirected;
+}
+
+impl<'a, V, E> Data for Undirected<'a, V, E> {
+    type NodeWeight = V;
+    type EdgeWeight = E;
+}
+
+impl<'a, V, E> DataMap for Undirected<'a, V, E> {
+    fn node_weight(&self, id: Id) -> Option<&V> {
+        self.graph.vertex_data(id)
+    }
+
+    fn edge_weight(&self, (a, b): (Id, Id)) -> Option<&E> {
+        if self.is_representative(a, b) {
+            self.graph.weight(a, b).or_else(|| self.graph.weight(b, a))
+        } else {
+            self.graph.weight(b, a)
+        }
+    }
+}
+
+impl<'a, V, E> Visitable for Undirected<'a, V, E> {
+    type Map = HashSet<Id>;
+
+    fn visit_map(&self) -> Self::Map {
+        HashSet::new()
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear()
+    }
+}
+
+impl<'a, V, E> NodeCount for Undirected<'a, V, E> {
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+}
+
+impl<'a, V, E> NodeIndexable for Undirected<'a, V, E> {
+    fn node_bound(&self) -> usize {
+        self.graph.node_bound()
+    }
+
+    fn to_index(&self, a: Id) -> usize {
+        self.graph.to_index(a)
+    }
+
+    fn from_index(&self, i: usize) -> Id {
+        self.graph.from_index(i)
+    }
+}
+
+impl<'a, V, E> NodeCompactIndexable for Undirected<'a, V, E> {}
+
+impl<'a, V, E> IntoNodeIdentifiers for Undirected<'a, V, E> {
+    type NodeIdentifiers = super::IdIter<'a, V, E>;
+
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        self.graph.ids()
+    }
+}
+
+impl<'a, V, E> IntoNeighbors for Undirected<'a, V, E> {
+    type Neighbors = Map<UndirectedEdges<'a, V, E>, fn((Id, Id, &'a E)) -> Id>;
+
+    fn neighbors(self, a: Id) -> Self::Neighbors {
+        self.edges(a).map(|(_, neighbor, _)| neighbor)
+    }
+}
+
+impl<'a, V, E> IntoEdgeReferences for Undirected<'a, V, E> {
+    type EdgeRef = (Id, Id, &'a E);
+    type EdgeReferences = UndirectedEdgeReferences<'a, V, E>;
+
+    fn edge_references(self) -> Self::EdgeReferences {
+        UndirectedEdgeReferences {
+            view: self,
+            edges: self.graph.edges(),
+        }
+    }
+}
+
+impl<'a, V, E> IntoEdges for Undirected<'a, V, E> {
+    type Edges = UndirectedEdges<'a, V, E>;
+
+    fn edges(self, a: Id) -> Self::Edges {
+        UndirectedEdges {
+            view: self,
+            id: a,
+            outbound: self.graph.outbound_edges(a),
+            incoming: self.graph.predecessors(a),
+        }
+    }
+}
+
+/// Iterates over every edge in an [Undirected](struct.Undirected.html) view, yielding each pair of connected vertices once.
+pub struct UndirectedEdgeReferences<'a, V, E> {
+    view: Undirected<'a, V, E>,
+    edges: EdgeIter<'a, V, E>,
+}
+
+impl<'a, V, E> Iterator for UndirectedEdgeReferences<'a, V, E> {
+    type Item = (Id, Id, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let view = self.view;
+        self.edges
+            .find(|&(source, sink, _)| view.is_representative(source, sink))
+    }
+}
+
+/// Iterates over the edges touching a vertex in an [Undirected](struct.Undirected.html) view, as (vertex, neighbor, weight).
+/// Each neighbor is yielded once: the edges that _start_ at the vertex come first, then the edges that _end_ at it from vertices it
+/// has no edge to.
+pub struct UndirectedEdges<'a, V, E> {
+    view: Undirected<'a, V, E>,
+    id: Id,
+    outbound: OutboundIter<'a, E>,
+    incoming: PredecessorIter<'a, V, E>,
+}
+
+impl<'a, V, E> Iterator for UndirectedEdges<'a, V, E> {
+    type Item = (Id, Id, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (view, id) = (self.view, self.id);
+        if let Some((_, sink, weight)) = self.outbound.next() {
+            if view.is_representative(id, sink) {
+                return Some((id, sink, weight));
+            }
+            return view.graph.weight(sink, id).map(|weight| (id, sink, weight));
+        }
+
+        self.incoming
+            .find(|&(source, _, _)| !view.graph.has_edge(id, source))
+            .map(|(source, _, weight)| (id, source, weight))
+    }
+}
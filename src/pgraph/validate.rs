@@ -0,0 +1,127 @@
+//! Consistency checks for the internal invariants of a `PGraph`.
+
+use super::{PGraph, ValidationError};
+
+impl<V, E> PGraph<V, E> {
+    /// Checks that the PGraph's internal bookkeeping is consistent:
+    /// - every vertex is stored in the slot matching its [Id](struct.Id.html)'s index,
+    /// - the list of empty slots matches the slots that are actually empty,
+    /// - every edge is stored under its sink's index and ends at a vertex that's in the PGraph,
+    /// - every vertex's cached edge count matches its edges, and
+    /// - every edge is in the reverse index, if the PGraph keeps one.
+    ///
+    /// A PGraph built and modified through its public methods always passes. This is meant as a debugging aid,
+    /// e.g. after building a PGraph through lower-level paths. It checks every vertex and edge, so it takes O(V + E) time.
+    ///
+    /// Returns every violation found, not just the first.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.remove_mut(ids[1]);
+    ///
+    /// assert_eq!(g.validate(), Ok(()));
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        for (index, slot) in self.guts.iter().enumerate() {
+            match slot {
+                Some(vertex) if vertex.id().index() != index => {
+                    errors.push(ValidationError::MisplacedVertex {
+                        index,
+                        id: vertex.id(),
+                    })
+                }
+                None if !self.empties.contains(&index) => {
+                    errors.push(ValidationError::UnlistedEmptySlot(index))
+                }
+                _ => (),
+            }
+        }
+        for &index in self.empties.iter() {
+            if let Some(None) = self.guts.get(index) {
+                continue;
+            }
+            errors.push(ValidationError::ListedNonEmptySlot(index));
+        }
+
+        for vertex in self {
+            let source = vertex.id();
+            let mut actual = 0;
+            for (slot, sink) in vertex.slotted_neighbor_ids() {
+                actual += 1;
+                if slot != sink.index() {
+                    errors.push(ValidationError::MisplacedEdge { source, sink, slot });
+                }
+                if sink.index() >= self.guts.len() {
+                    errors.push(ValidationError::EdgeOutOfBounds { source, sink });
+                } else if !self.has_vertex(sink) {
+                    errors.push(ValidationError::DanglingEdge { source, sink });
+                }
+                if let Some(reverse) = &self.reverse {
+                    let indexed = matches!(reverse.get(sink.index()), Some(sources) if sources.contains(&source));
+                    if !indexed {
+                        errors.push(ValidationError::UnindexedEdge { source, sink });
+                    }
+                }
+            }
+
+            let cached = vertex.len_neighbors();
+            if cached != actual {
+                errors.push(ValidationError::EdgeCountMismatch {
+                    id: source,
+                    cached,
+                    actual,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pgraph::Vertex;
+
+    #[test]
+    fn test_detects_violations() {
+        let mut g = PGraph::<usize, usize>::with_reverse_index();
+        let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+        g.connect_mut(ids[0], ids[1], 12);
+        g.connect_mut(ids[0], ids[2], 13);
+
+        // Put a copy of the first vertex in the last slot, and forget to record a removal.
+        g.guts.set(2, Some(Vertex::from(ids[0], 1)));
+        g.guts.set(1, None);
+        g.empties.insert(0);
+        g.reverse = Some(Default::default());
+
+        let errors = g.validate().unwrap_err();
+        assert!(errors.contains(&ValidationError::MisplacedVertex {
+            index: 2,
+            id: ids[0]
+        }));
+        assert!(errors.contains(&ValidationError::UnlistedEmptySlot(1)));
+        assert!(errors.contains(&ValidationError::ListedNonEmptySlot(0)));
+        assert!(errors.contains(&ValidationError::DanglingEdge {
+            source: ids[0],
+            sink: ids[1]
+        }));
+        assert!(errors.contains(&ValidationError::UnindexedEdge {
+            source: ids[0],
+            sink: ids[2]
+        }));
+    }
+}
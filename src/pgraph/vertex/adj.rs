@@ -1,4 +1,6 @@
+use super::OwnedEdges;
 use crate::id::Id;
+use crate::pgraph::slots_mut;
 use im::{vector, Vector};
 use std::borrow::Borrow;
 use std::fmt::{Debug, Error, Formatter};
@@ -12,6 +14,7 @@ pub type Edge<E> = (Id, Arc<E>);
 /// Struct to manage a vertex's adjacencies without having to care about the vertex itself.
 pub(super) struct AdjList<E> {
     edges: Vector<Option<Edge<E>>>,
+    len: usize,
 }
 
 // Derive only implements for <E: Clone> because of rust#26925
@@ -19,6 +22,7 @@ impl<E> Clone for AdjList<E> {
     fn clone(&self) -> Self {
         AdjList {
             edges: self.edges.clone(),
+            len: self.len,
         }
     }
 }
@@ -44,15 +48,56 @@ impl<E> AdjList<E> {
     pub(super) fn new() -> Self {
         AdjList {
             edges: Vector::new(),
+            len: 0,
         }
     }
 
+    /// Consumes the list and returns its edges, in order. Weights that aren't shared with another `AdjList` are moved out;
+    /// shared ones are returned as their shared handles.
+    pub(super) fn into_edges(self) -> OwnedEdges<E> {
+        self.edges
+            .into_iter()
+            .flatten()
+            .map(|(sink, weight)| (sink, Arc::try_unwrap(weight)))
+            .collect()
+    }
+
     /// Counts the number of neighbors.
     ///
-    /// Takes O(N) time where N is the maximum number of vertices that _have ever been_ in the graph.  
-    /// TODO: Store this if it comes up a lot?
+    /// Runs in O(1), since the count is kept up to date as edges are added and removed.
     pub(super) fn len(&self) -> usize {
-        self.into_iter().count()
+        self.len
+    }
+
+    /// Counts the slots (empty or not) in the backing vector, i.e. one more than the highest sink index it has room for.
+    pub(super) fn slots(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Extends the backing vector with empty slots for edges to sinks with indices up to and including `up_to_index`.
+    /// Does nothing if it's already long enough.
+    ///
+    /// Runs in O(`up_to_index`)
+    pub(super) fn reserve(&mut self, up_to_index: usize) {
+        while self.edges.len() <= up_to_index {
+            self.edges.push_back(None);
+        }
+    }
+
+    /// Returns true iff the backing vector ends in an empty slot, i.e. `shrink_to_fit` would free something.
+    ///
+    /// Runs in O(1)
+    pub(super) fn can_shrink(&self) -> bool {
+        matches!(self.edges.last(), Some(None))
+    }
+
+    /// Removes the empty slots from the end of the backing vector, leaving the slots before the last edge alone.
+    ///
+    /// Runs in O(K), where K is the number of slots removed.
+    pub(super) fn shrink_to_fit(&mut self) {
+        while let Some(None) = self.edges.last() {
+            self.edges.pop_back();
+        }
     }
 
     /// Returns true iff there exists an `Edge` that goes to `sink`.
@@ -91,12 +136,111 @@ impl<E> AdjList<E> {
         }
 
         let element = self.edges.get_mut(sink.index()).unwrap();
-        element.replace((*sink, Arc::new(weight)));
+        if element.replace((*sink, Arc::new(weight))).is_none() {
+            self.len += 1;
+        }
 
         let (_, weight_arc) = element.as_mut().unwrap();
         Arc::get_mut(weight_arc).unwrap()
     }
 
+    /// Deletes the edge that ends at `sink`. Returns false iff that edge didn't exist to begin with.
+    ///
+    /// Runs in O(1)
+    pub(super) fn disconnect_edge<T: Borrow<Id>>(&mut self, sink: T) -> bool {
+        let sink = sink.borrow();
+        let e = self.edges.get_mut(sink.index());
+
+        let mut result = false;
+        if let Some(edge) = e {
+            let take = if let Some((id, _)) = edge {
+                sink == id
+            } else {
+                false
+            };
+
+            if take {
+                edge.take();
+                self.len -= 1;
+                result = true;
+            }
+        };
+        result
+    }
+
+    /// Deletes the edge that ends at `sink` and returns the handle to its weight, or `None` if that edge didn't exist.
+    ///
+    /// Runs in O(1)
+    pub(super) fn take_edge<T: Borrow<Id>>(&mut self, sink: T) -> Option<Arc<E>> {
+        let sink = sink.borrow();
+        let slot = self.edges.get_mut(sink.index())?;
+        if slot.as_ref().is_some_and(|(id, _)| sink == id) {
+            self.len -= 1;
+            slot.take().map(|(_, weight)| weight)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new `AdjList` with an edge to each of this list's sinks, whose weight is the result of calling `f` with the
+    /// sink and this list's weight. The new list uses the same slots as this one.
+    ///
+    /// Runs in O(N), where N is the number of slots in the backing vector.
+    pub(super) fn map<G, F: FnMut(Id, &E) -> G>(&self, mut f: F) -> AdjList<G> {
+        AdjList {
+            edges: self
+                .edges
+                .iter()
+                .map(|e| {
+                    e.as_ref()
+                        .map(|(id, weight)| (*id, Arc::new(f(*id, weight))))
+                })
+                .collect(),
+            len: self.len,
+        }
+    }
+
+    /// Deletes every edge for which `pred` returns `true` when called with the edge's sink and weight.
+    /// Returns the number of edges deleted.
+    ///
+    /// Runs in O(N), where N is the number of slots in the backing vector.
+    pub(super) fn disconnect_where<F: FnMut(Id, &E) -> bool>(&mut self, mut pred: F) -> usize {
+        let mut removed = 0;
+        for edge in self.edges.iter_mut() {
+            if matches!(edge, Some((id, weight)) if pred(*id, weight)) {
+                *edge = None;
+                removed += 1;
+            }
+        }
+        self.len -= removed;
+        removed
+    }
+
+    /// Returns an iterator over the sinks of every edge, paired with the index of the slot the edge is stored in.
+    /// (These should always match; this is for checking that they do.)
+    pub(super) fn slotted_ids(&self) -> impl Iterator<Item = (usize, Id)> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, e)| e.as_ref().map(|(id, _)| (slot, *id)))
+    }
+
+    /// Returns an iterator over the edges whose sinks have an index greater than `after_index`, without walking the slots before them.
+    pub(super) fn iter_after(&self, after_index: usize) -> Iter<'_, E> {
+        let start = after_index.saturating_add(1);
+        let slots = match self.edges.len() {
+            len if start < len => self.edges.focus().narrow(start..).into_iter(),
+            0 => self.edges.iter(),
+            len => {
+                // `narrow` can't make an empty focus, so step past the last slot instead.
+                let mut slots = self.edges.focus().narrow(len - 1..).into_iter();
+                slots.next();
+                slots
+            }
+        };
+        slots.filter_map(iter_item)
+    }
+
     pub(super) fn id_iter(&self) -> IdIter<E> {
         self.edges.iter().filter_map(|e| {
             if let Some((id, _)) = e.as_ref() {
@@ -132,13 +276,15 @@ impl<E: Clone> AdjList<E> {
         }
     }
 
-    /// Gets a mutable reference to the weight of the edge that ends at `sink`, or `None` if no such edge exists.
+    /// Gets a mutable reference to the weight of the edge that ends at `sink`, first creating the edge with the
+    /// result of `default` if it doesn't exist.
     ///
-    /// Runs in O(1)
-    pub(super) fn make_edge_mut<T: Borrow<Id>>(
+    /// Worst case runs in O(N), where N is the maximum number of vertices *currently* in the graph. Amortized O(1).
+    pub(super) fn get_or_insert_with<T: Borrow<Id>, F: FnOnce() -> E>(
         &mut self,
         sink: T,
-    ) -> &mut std::option::Option<(Id, Arc<E>)> {
+        default: F,
+    ) -> &mut E {
         let sink = sink.borrow();
         self.edges = self.edges.clone();
 
@@ -146,30 +292,34 @@ impl<E: Clone> AdjList<E> {
             self.edges.push_back(None);
         }
 
-        &mut self.edges[sink.index()]
+        let element = &mut self.edges[sink.index()];
+        if element.is_none() {
+            self.len += 1;
+        }
+
+        let (_, weight_arc) = element.get_or_insert_with(|| (*sink, Arc::new(default())));
+        Arc::make_mut(weight_arc)
     }
 
-    /// Deletes the edge that ends at `sink`. Returns false iff that edge didn't exist to begin with.
+    /// Gets mutable references to the weights of the edges that end at each of `sinks`, which have to be in increasing order
+    /// of index. Sinks without an edge are skipped.
     ///
-    /// Runs in O(1)
-    pub(super) fn disconnect_edge<T: Borrow<Id>>(&mut self, sink: T) -> bool {
-        let sink = sink.borrow();
-        let e = self.edges.get_mut(sink.index());
-
-        let mut result = false;
-        if let Some(edge) = e {
-            let take = if let Some((id, _)) = edge {
-                sink == id
-            } else {
-                false
-            };
-
-            if take {
-                edge.take();
-                result = true;
-            }
-        };
-        result
+    /// Runs in O(K log N), where K is the number of sinks.
+    pub(super) fn weights_many_mut(&mut self, sinks: &[Id]) -> Vec<&mut E> {
+        let indices: Vec<usize> = sinks
+            .iter()
+            .map(Id::index)
+            .take_while(|&index| index < self.edges.len())
+            .collect();
+
+        slots_mut(&mut self.edges, &indices)
+            .into_iter()
+            .zip(sinks)
+            .filter_map(|(slot, sink)| match slot {
+                Some((id, weight)) if id == sink => Some(Arc::make_mut(weight)),
+                _ => None,
+            })
+            .collect()
     }
 }
 
@@ -178,16 +328,13 @@ impl<E: PartialEq<F>, F> PartialEq<AdjList<F>> for AdjList<E> {
         let mut iter1 = self.into_iter();
         let mut iter2 = other.into_iter();
 
-        let item1 = iter1.next();
-        let item2 = iter2.next();
-
         loop {
-            match item1 {
-                Some((id1, e1)) => match item2 {
+            match iter1.next() {
+                Some((id1, e1)) => match iter2.next() {
                     Some((id2, e2)) if id1 == id2 && e1 == e2 => (),
                     _ => break false,
                 },
-                None => match item2 {
+                None => match iter2.next() {
                     Some(_) => break false,
                     None => break true,
                 },
@@ -216,17 +363,19 @@ pub type Iter<'a, E> = FilterMap<
     fn(&'a Option<Edge<E>>) -> Option<IterItem<'a, E>>,
 >;
 
+fn iter_item<E>(e: &Option<Edge<E>>) -> Option<IterItem<'_, E>> {
+    if let Some((id, arc_weight)) = e.as_ref() {
+        Some((*id, &**arc_weight))
+    } else {
+        None
+    }
+}
+
 impl<'a, E> IntoIterator for &'a AdjList<E> {
     type Item = IterItem<'a, E>;
     type IntoIter = Iter<'a, E>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.edges.iter().filter_map(|e| {
-            if let Some((id, arc_weight)) = e.as_ref() {
-                Some((*id, &**arc_weight))
-            } else {
-                None
-            }
-        })
+        self.edges.iter().filter_map(iter_item)
     }
 }
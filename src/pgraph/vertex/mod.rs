@@ -8,6 +8,9 @@ use std::sync::Arc;
 
 pub mod adj;
 
+/// A vertex's outbound edges as `(sink, weight)` pairs, with each weight moved out if it isn't shared.
+type OwnedEdges<E> = Vec<(Id, Result<E, Arc<E>>)>;
+
 /// Holds the data for a single PGraph vertex. Contains the vertex [Id](struct.Id.html), the vertex's data, and the vertex's neighbors.
 pub struct Vertex<V, E> {
     id: Id,
@@ -15,6 +18,22 @@ pub struct Vertex<V, E> {
     adj: AdjList<E>,
 }
 
+/// Everything removed from a PGraph along with a vertex, as returned by
+/// [`remove_returning_mut`](struct.PGraph.html#method.remove_returning_mut).
+///
+/// A self-loop on the vertex appears in both `outbound` and `inbound`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemovedVertex<V, E> {
+    /// The [Id](struct.Id.html) the vertex had. It's no longer valid in the PGraph it was removed from.
+    pub id: Id,
+    /// The vertex's data.
+    pub data: V,
+    /// The sinks and weights of the edges that _started_ at the vertex, ordered by sink.
+    pub outbound: Vec<(Id, E)>,
+    /// The sources and weights of the edges that _ended_ at the vertex.
+    pub inbound: Vec<(Id, E)>,
+}
+
 impl<V: Debug, E: Debug> Debug for Vertex<V, E> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "{:?} ({:?}) => ({:?})", self.data, self.id, self.adj)
@@ -56,6 +75,40 @@ impl<V, E> Vertex<V, E> {
         self.id
     }
 
+    /// Consumes this vertex and returns its data, without cloning it. If the data is shared with another vertex (e.g. the
+    /// same vertex in a clone of the PGraph), returns the shared handle to it instead.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<String, usize>::new();
+    /// let id = g.add_mut("data".to_string());
+    ///
+    /// let v = g[id].clone();
+    /// assert_eq!(*v.clone().into_data().unwrap_err(), "data");
+    ///
+    /// drop(g);
+    /// assert_eq!(v.into_data().unwrap(), "data");
+    /// # }
+    /// ```
+    pub fn into_data(self) -> Result<V, Arc<V>> {
+        Arc::try_unwrap(self.data)
+    }
+
+    /// Consumes this vertex and returns its data along with its outbound edges as `(sink, weight)` pairs, in iteration order.
+    /// Like `into_data`, anything shared with another vertex is returned as its shared handle instead.
+    pub(super) fn into_parts(self) -> (Result<V, Arc<V>>, OwnedEdges<E>) {
+        (Arc::try_unwrap(self.data), self.adj.into_edges())
+    }
+
+    /// Removes the edge from this vertex to `sink`.
+    ///
+    /// Returns `true` iff the edge existed to be removed.
+    pub fn disconnect<T: Borrow<Id>>(&mut self, sink: T) -> bool {
+        self.adj.disconnect_edge(sink)
+    }
+
     /// Returns the weight of the edge from this vertex to `sink`, or `None` if such an edge doesn't exist.
     #[must_use]
     pub fn weight<T: Borrow<Id>>(&self, sink: T) -> Option<&E> {
@@ -74,6 +127,91 @@ impl<V, E> Vertex<V, E> {
         self.adj.len()
     }
 
+    /// Returns `true` iff this vertex has at least one outgoing edge.
+    #[must_use]
+    pub fn is_source(&self) -> bool {
+        self.len_neighbors() > 0
+    }
+
+    /// Copies this vertex's outgoing edges into a contiguous `Vec`, as (sink: Id, edge: &E), in the same order as iterating over the vertex.
+    ///
+    /// Iterating over a vertex walks its whole adjacency list, including the empty slots, through `im`'s tree. Collecting the
+    /// edges once costs an allocation, but makes every later pass over them a plain slice traversal, which pays off for
+    /// algorithms that go over the same vertex's neighbors many times.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.connect_mut(ids[0], ids[1], 12);
+    ///
+    /// let edges = g[ids[0]].edges_compact();
+    /// assert_eq!(edges, vec![(ids[1], &12), (ids[2], &13)]);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn edges_compact(&self) -> Vec<(Id, &E)> {
+        let mut edges = Vec::with_capacity(self.len_neighbors());
+        edges.extend(self);
+        edges
+    }
+
+    /// Counts the slots in this vertex's adjacency list, i.e. one more than the highest sink index it has room for.
+    #[must_use]
+    pub(crate) fn neighbor_slots(&self) -> usize {
+        self.adj.slots()
+    }
+
+    /// Pre-extends this vertex's adjacency list with empty slots for edges to every vertex with an index up to and including
+    /// `up_to_index`. Has no effect on the vertex's edges.
+    ///
+    /// This is `pub(crate)` instead of `pub` because the vertex doesn't know how many slots the PGraph has, so the caller has to
+    /// clamp `up_to_index` (see `PGraph::reserve_neighbors`).
+    pub(crate) fn reserve_neighbors(&mut self, up_to_index: usize) {
+        self.adj.reserve(up_to_index);
+    }
+
+    /// Trims the empty slots at the end of this vertex's adjacency list, like the ones left behind after its highest-index sinks are
+    /// disconnected or added by `PGraph::reserve_neighbors`. Never removes an edge: the empty slots before its last edge are left alone,
+    /// so every edge still works.
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, usize>::new();
+    /// let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    ///
+    /// g.connect_mut(ids[0], ids[1], 12);
+    /// g.connect_mut(ids[0], ids[2], 13);
+    /// g.disconnect_mut(ids[0], ids[2]);
+    ///
+    /// g[ids[0]].shrink_neighbors();
+    /// assert_eq!(g[(ids[0], ids[1])], 12);
+    /// # }
+    /// ```
+    pub fn shrink_neighbors(&mut self) {
+        self.adj.shrink_to_fit();
+    }
+
+    /// Returns true iff `shrink_neighbors` would free any slots.
+    pub(crate) fn can_shrink_neighbors(&self) -> bool {
+        self.adj.can_shrink()
+    }
+
+    /// Creates a vertex with the same Id and edge sinks as this one, holding `data`, whose edges' weights are the results
+    /// of calling `edge_map` with each sink and this vertex's weight for it.
+    pub(super) fn map<W, G, F: FnMut(Id, &E) -> G>(&self, data: W, edge_map: F) -> Vertex<W, G> {
+        Vertex {
+            id: self.id,
+            data: Arc::new(data),
+            adj: self.adj.map(edge_map),
+        }
+    }
+
     /// Creates a vertex from an Id and vertex data. The vertex starts with no neighbors.
     #[must_use]
     pub(super) fn from(id: Id, data: V) -> Self {
@@ -97,9 +235,40 @@ impl<V, E> Vertex<V, E> {
         self.adj.add_edge(sink, weight)
     }
 
+    /// Removes every edge from this vertex for which `pred` returns `true` when called with the edge's sink and weight.
+    /// Returns the number of edges removed.
+    pub(super) fn disconnect_where<F: FnMut(Id, &E) -> bool>(&mut self, pred: F) -> usize {
+        self.adj.disconnect_where(pred)
+    }
+
+    /// Returns an iterator over this vertex's outgoing edges to sinks with a greater index than `sink`'s.
+    pub(super) fn neighbors_after(&self, sink: Id) -> adj::Iter<'_, E> {
+        self.adj.iter_after(sink.index())
+    }
+
     pub(super) fn neighbor_ids(&self) -> IdIter<E> {
         self.adj.id_iter()
     }
+
+    /// Returns an iterator over the sinks of this vertex's edges, paired with the index of the adjacency slot each edge is stored in.
+    pub(super) fn slotted_neighbor_ids(&self) -> impl Iterator<Item = (usize, Id)> + '_ {
+        self.adj.slotted_ids()
+    }
+
+    /// Returns a new handle to the (shared) data on this vertex, without cloning the data itself.
+    pub(super) fn shared_data(&self) -> Arc<V> {
+        Arc::clone(&self.data)
+    }
+
+    /// Replaces the data on this vertex with `data`, returning the old data.
+    pub(super) fn replace_data(&mut self, data: Arc<V>) -> Arc<V> {
+        std::mem::replace(&mut self.data, data)
+    }
+
+    /// Returns `true` iff this vertex holds the only handle to its data.
+    pub(super) fn has_unique_data(&self) -> bool {
+        Arc::strong_count(&self.data) == 1
+    }
 }
 
 impl<V: Clone, E> Vertex<V, E> {
@@ -111,24 +280,62 @@ impl<V: Clone, E> Vertex<V, E> {
 }
 
 impl<V, E: Clone> Vertex<V, E> {
+    /// Returns an iterator over this vertex's outgoing edges that yields owned copies of their weights.
+    /// The iterator's items are (sink: Id, edge: E)
+    /// # Examples
+    ///
+    /// ```
+    /// # use pgraph::PGraph;
+    /// # fn main() {
+    /// let mut g = PGraph::<usize, String>::new();
+    /// let id1 = g.add_mut(1);
+    /// let id2 = g.add_mut(2);
+    /// g.connect_mut(id1, id2, "12".to_string());
+    /// g.connect_mut(id1, id1, "11".to_string());
+    ///
+    /// let neighbors: Vec<(_, String)> = g[id1].neighbors_cloned().collect();
+    /// g.remove_mut(id2);
+    ///
+    /// assert_eq!(neighbors, vec![(id1, "11".to_string()), (id2, "12".to_string())]);
+    /// # }
+    /// ```
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn neighbors_cloned(&self) -> impl Iterator<Item = (Id, E)> + '_ {
+        self.into_iter()
+            .map(|(sink, weight)| (sink, weight.clone()))
+    }
+
     /// Returns a mutable reference to the weight of the edge from this vertex to sink, or `None` if one doesn't exist.
     #[must_use]
     pub fn weight_mut<T: Borrow<Id>>(&mut self, sink: T) -> Option<&mut E> {
         self.adj.weight_mut(sink)
     }
 
-    pub(crate) fn make_edge_mut<T: Borrow<Id>>(
-        &mut self,
-        sink: T,
-    ) -> &mut std::option::Option<(Id, Arc<E>)> {
-        self.adj.make_edge_mut(sink)
+    /// Removes the edge from this vertex to `sink` and returns its weight, which is moved out unless it's shared with another
+    /// vertex. Returns `None` if there's no such edge.
+    ///
+    /// This is `pub(super)` instead of `pub` because it doesn't update the PGraph's indices, so the caller has to put the edge back.
+    pub(super) fn take_weight<T: Borrow<Id>>(&mut self, sink: T) -> Option<E> {
+        let weight = self.adj.take_edge(sink)?;
+        Some(Arc::try_unwrap(weight).unwrap_or_else(|shared| (*shared).clone()))
     }
 
-    /// Removes the edge from this vertex to `sink`.
+    /// Returns mutable references to the weights of the edges from this vertex to each of `sinks`, which have to be in
+    /// increasing order of index. Sinks without an edge are skipped.
+    pub(super) fn weights_many_mut(&mut self, sinks: &[Id]) -> Vec<&mut E> {
+        self.adj.weights_many_mut(sinks)
+    }
+
+    /// Returns a mutable reference to the weight of the edge from this vertex to `sink`, creating that edge with the
+    /// result of `default` first if it doesn't exist.
     ///
-    /// Returns `true` iff the edge existed to be removed.
-    pub fn disconnect<T: Borrow<Id>>(&mut self, sink: T) -> bool {
-        self.adj.disconnect_edge(sink)
+    /// This is `pub(crate)` instead of `pub` because the vertex has no way to check whether `sink` actually exists in the PGraph.
+    pub(crate) fn weight_or_insert_with<T: Borrow<Id>, F: FnOnce() -> E>(
+        &mut self,
+        sink: T,
+        default: F,
+    ) -> &mut E {
+        self.adj.get_or_insert_with(sink, default)
     }
 }
 
@@ -0,0 +1,262 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn test_reachable() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let set = |v: &[Id]| -> HashSet<Id> { v.iter().cloned().collect() };
+    assert_eq!(g.reachable(ids[0]), set(&ids[1..]));
+    assert_eq!(g.reachable(ids[1]), set(&ids[1..]));
+    assert_eq!(g.reachable(ids[3]), set(&ids[1..]));
+    assert!(g.reachable(b_ids[0]).is_empty());
+
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[3], ids[1]);
+    assert_eq!(g.reachable(ids[1]), set(&ids[2..]));
+
+    g.connect_mut(ids[3], ids[3], 44);
+    assert_eq!(g.reachable(ids[3]), set(&ids[3..]));
+}
+
+#[test]
+fn test_eccentricity() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    assert_eq!(g.eccentricity(ids[0]), Some(3));
+    assert_eq!(g.eccentricity(ids[1]), Some(2));
+    assert_eq!(g.eccentricity(ids[2]), Some(1));
+    assert_eq!(g.eccentricity(ids[3]), Some(2));
+    assert_eq!(g.eccentricity(b_ids[0]), None);
+
+    let isolated = g.add_mut(5);
+    assert_eq!(g.eccentricity(isolated), Some(0));
+}
+
+#[test]
+fn test_diameter() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    // Nothing can reach ids[0]
+    assert_eq!(g.diameter(), None);
+
+    g.connect_mut(ids[1], ids[0], 21);
+    assert_eq!(g.diameter(), Some(3));
+
+    g.connect_mut(ids[3], ids[0], 41);
+    assert_eq!(g.diameter(), Some(3));
+
+    g.connect_mut(ids[0], ids[2], 13);
+    g.connect_mut(ids[0], ids[3], 14);
+    assert_eq!(g.diameter(), Some(2));
+
+    let mut single = PGraph::<usize, usize>::new();
+    assert_eq!(single.diameter(), None);
+    single.add_mut(1);
+    assert_eq!(single.diameter(), Some(0));
+}
+
+#[test]
+fn test_complement() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
+    g.remove_mut(ids[0]);
+
+    let c = g.complement();
+    assert_eq!(c.edge_count(), 3 * 2 - 4);
+    for source in g.ids() {
+        for sink in g.ids() {
+            let expected = source != sink && !g.has_edge(source, sink);
+            assert_eq!(c.has_edge(source, sink), expected);
+        }
+    }
+    assert_eq!(c[(ids[1], ids[3])], 0);
+
+    let c = g.complement_with_self_loops();
+    assert_eq!(c.edge_count(), 3 * 3 - 5);
+    for source in g.ids() {
+        for sink in g.ids() {
+            assert_eq!(c.has_edge(source, sink), !g.has_edge(source, sink));
+        }
+    }
+}
+
+#[test]
+fn test_path_cost() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    assert_eq!(g.path_cost(&ids), Some(12 + 23 + 34));
+    assert_eq!(
+        g.path_cost(vec![ids[1], ids[2], ids[1], ids[2]]),
+        Some(23 + 32 + 23)
+    );
+    assert_eq!(
+        g.path_cost([ids[3], ids[1], ids[2], ids[3], ids[1]]),
+        Some(42 + 23 + 34 + 42)
+    );
+    assert_eq!(g.path_cost([ids[1], ids[0]]), None);
+    assert_eq!(g.path_cost([ids[0], b_ids[1]]), None);
+
+    assert_eq!(g.path_cost([ids[3]]), Some(0));
+    assert_eq!(g.path_cost([b_ids[3]]), None);
+    g.remove_mut(ids[3]);
+    assert_eq!(g.path_cost([ids[3]]), None);
+    assert_eq!(g.path_cost(Vec::<Id>::new()), Some(0));
+}
+
+#[test]
+fn test_expand_frontier() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let set = |v: &[Id]| -> HashSet<Id> { v.iter().cloned().collect() };
+    assert_eq!(g.expand_frontier(vec![ids[0]]), set(&ids[1..2]));
+    assert_eq!(
+        g.expand_frontier(vec![ids[0], ids[1], ids[3]]),
+        set(&ids[1..3])
+    );
+    assert_eq!(
+        g.expand_frontier(vec![ids[2], ids[3], b_ids[0]]),
+        set(&[ids[1], ids[3]])
+    );
+    assert_eq!(g.expand_frontier(vec![ids[1], ids[1]]), set(&ids[2..3]));
+    assert!(g.expand_frontier(b_ids).is_empty());
+    assert!(g.expand_frontier(vec![]).is_empty());
+
+    // A level-by-level BFS from several sources reaches the same vertices as `reachable` does from each.
+    let mut seen = set(&[ids[0], ids[3]]);
+    let mut frontier = seen.clone();
+    while !frontier.is_empty() {
+        frontier = g.expand_frontier(frontier);
+        frontier.retain(|id| seen.insert(*id));
+    }
+    assert_eq!(seen, set(&ids));
+}
+
+#[test]
+fn test_descendants_ancestors() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let set = |v: &[Id]| -> HashSet<Id> { v.iter().cloned().collect() };
+    assert_eq!(g.descendants(ids[0]), g.reachable(ids[0]));
+    assert_eq!(g.ancestors(ids[0]), set(&[]));
+    assert_eq!(g.ancestors(ids[1]), set(&ids));
+    assert_eq!(g.ancestors(ids[3]), set(&ids));
+    assert!(g.ancestors(b_ids[1]).is_empty());
+
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[3], ids[1]);
+    assert_eq!(g.ancestors(ids[2]), set(&ids[..2]));
+    assert_eq!(g.descendants(ids[1]), set(&ids[2..]));
+
+    for &id in &ids {
+        for &other in &ids {
+            assert_eq!(
+                g.descendants(id).contains(&other),
+                g.ancestors(other).contains(&id)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_transitive_reduction() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    assert!(g.transitive_reduction().is_err());
+
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[3], ids[1]);
+    g.connect_mut(ids[0], ids[2], 13);
+    g.connect_mut(ids[0], ids[3], 14);
+    g.connect_mut(ids[1], ids[3], 24);
+    let reduced = g.transitive_reduction().unwrap();
+    assert_eq!(
+        reduced.edges().collect::<Vec<_>>(),
+        vec![
+            (ids[0], ids[1], &12),
+            (ids[1], ids[2], &23),
+            (ids[2], ids[3], &34)
+        ]
+    );
+    for &id in &ids {
+        assert_eq!(reduced.reachable(id), g.reachable(id));
+    }
+    assert_eq!(g.edge_count(), 6);
+
+    // Edges that aren't implied by other paths stay, even when they skip over vertices in the topological order.
+    let mut h = PGraph::<usize, usize>::new();
+    let h_ids: Vec<_> = h.add_all_mut(vec![1usize, 2, 3, 4]);
+    h.connect_mut(h_ids[0], h_ids[1], 12);
+    h.connect_mut(h_ids[0], h_ids[2], 13);
+    h.connect_mut(h_ids[2], h_ids[3], 34);
+    h.connect_mut(h_ids[1], h_ids[3], 24);
+    assert_eq!(h.transitive_reduction().unwrap().edge_count(), 4);
+
+    h.connect_mut(h_ids[3], h_ids[3], 44);
+    assert_eq!(
+        h.transitive_reduction().unwrap_err(),
+        CycleError { vertex: h_ids[3] }
+    );
+}
+
+#[test]
+fn test_shortest_path_tree() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let tree = g.shortest_path_tree(ids[0]);
+    let mut expected = HashMap::new();
+    expected.insert(ids[1], (ids[0], 12));
+    expected.insert(ids[2], (ids[1], 35));
+    expected.insert(ids[3], (ids[2], 69));
+    assert_eq!(tree, expected);
+
+    g.connect_mut(ids[0], ids[3], 50);
+    g.connect_mut(ids[3], ids[2], 1);
+    let tree = g.shortest_path_tree(ids[0]);
+    assert_eq!(tree[&ids[3]], (ids[0], 50));
+    assert_eq!(tree[&ids[2]], (ids[1], 35));
+    assert_eq!(tree[&ids[1]], (ids[0], 12));
+
+    assert!(!g.shortest_path_tree(ids[1]).contains_key(&ids[0]));
+    assert!(g.shortest_path_tree(b_ids[0]).is_empty());
+}
+
+#[test]
+fn test_core_number_and_k_core() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let extra = g.add_mut(5);
+    g.connect_mut(extra, extra, 55);
+
+    let cores = g.core_number();
+    assert_eq!(cores.len(), 5);
+    assert_eq!(cores[&ids[0]], 1);
+    assert_eq!(cores[&ids[1]], 2);
+    assert_eq!(cores[&ids[2]], 2);
+    assert_eq!(cores[&ids[3]], 2);
+    assert_eq!(cores[&extra], 0);
+
+    let core = g.k_core(2);
+    assert_eq!(core.ids().collect::<Vec<_>>(), ids[1..].to_vec());
+    assert_eq!(core.edge_count(), 4);
+    assert_eq!(core[(ids[3], ids[1])], 42);
+
+    assert_eq!(g.k_core(0).ids().count(), 5);
+    assert_eq!(g.k_core(1).ids().count(), 4);
+    assert_eq!(g.k_core(3).ids().count(), 0);
+    assert!(PGraph::<usize, usize>::new().core_number().is_empty());
+}
@@ -0,0 +1,71 @@
+use super::*;
+
+fn byte_graph() -> (Vec<Id>, PGraph<Vec<u8>, Vec<u8>>) {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 300);
+    let g = g.map(
+        |_, &data| vec![data as u8],
+        |_, _, &weight| (weight as u16).to_le_bytes().to_vec(),
+    );
+    (ids, g)
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    let (ids, mut g) = byte_graph();
+    g.remove_mut(ids[0]);
+
+    let bytes = g.to_bytes();
+    let h = PGraph::<Vec<u8>, Vec<u8>>::from_bytes(&bytes).unwrap();
+    assert!(h == g);
+    assert_eq!(h.to_bytes(), bytes);
+    assert!(ids.iter().all(|id| !h.has_vertex(id)));
+
+    let h_ids: Vec<_> = h.ids().collect();
+    assert_eq!(h[(h_ids[0],)], vec![2]);
+    assert_eq!(h[(h_ids[2], h_ids[2])], 300u16.to_le_bytes().to_vec());
+    assert_eq!(h.edge_count(), g.edge_count());
+
+    let empty = PGraph::<Vec<u8>, Vec<u8>>::new();
+    let decoded = PGraph::<Vec<u8>, Vec<u8>>::from_bytes(&empty.to_bytes()).unwrap();
+    assert_eq!(decoded.ids().count(), 0);
+}
+
+#[test]
+fn test_bytes_errors() {
+    let (_, g) = byte_graph();
+    let bytes = g.to_bytes();
+    let decode = PGraph::<Vec<u8>, Vec<u8>>::from_bytes;
+
+    assert_eq!(decode(&bytes[1..]).unwrap_err(), DecodeError::BadHeader);
+    assert_eq!(decode(&[]).unwrap_err(), DecodeError::BadHeader);
+    for end in 4..bytes.len() {
+        assert_eq!(
+            decode(&bytes[..end]).unwrap_err(),
+            DecodeError::UnexpectedEnd
+        );
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.extend_from_slice(&[0, 0]);
+    assert_eq!(
+        decode(&trailing).unwrap_err(),
+        DecodeError::TrailingBytes { extra: 2 }
+    );
+
+    let mut header = bytes[..4].to_vec();
+    header.extend_from_slice(&[1, 0, 1, 5, 0]);
+    assert_eq!(
+        decode(&header).unwrap_err(),
+        DecodeError::SinkOutOfRange {
+            sink: 5,
+            vertices: 1
+        }
+    );
+
+    let mut header = bytes[..4].to_vec();
+    header.extend_from_slice(&[0xff; 10]);
+    header.push(1);
+    assert_eq!(decode(&header).unwrap_err(), DecodeError::Overflow);
+}
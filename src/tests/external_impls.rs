@@ -0,0 +1,270 @@
+use super::*;
+use petgraph::data::{DataMap, DataMapMut};
+use petgraph::visit::{
+    GetAdjacencyMatrix, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, NodeCount, NodeFiltered,
+    NodeIndexable, Reversed,
+};
+use petgraph::Direction;
+
+#[test]
+fn test_data_map() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let filtered = NodeFiltered::from_fn(&g, |id| id != ids[0]);
+    assert!(filtered.node_weight(ids[0]).is_none());
+    assert_eq!(filtered.node_weight(ids[1]), Some(&2));
+    assert_eq!(filtered.edge_weight((ids[1], ids[2])), Some(&23));
+    assert!(filtered.edge_weight((ids[2], ids[0])).is_none());
+
+    let reversed = Reversed(&g);
+    assert_eq!(reversed.node_weight(ids[3]), Some(&4));
+    assert_eq!(reversed.edge_weight((ids[2], ids[3])), Some(&34));
+}
+
+#[test]
+fn test_data_map_mut() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    *g.node_weight_mut(ids[0]).unwrap() *= 10;
+    *g.edge_weight_mut((ids[0], ids[1])).unwrap() += 1;
+
+    assert_eq!(g[(ids[0],)], 10);
+    assert_eq!(g[(ids[0], ids[1])], 13);
+    assert!(g.node_weight_mut(b_ids[0]).is_none());
+    assert!(g.edge_weight_mut((ids[1], ids[0])).is_none());
+}
+
+#[test]
+fn test_edge_count() {
+    let (ids, mut g) = create_vertices();
+    assert_eq!(g.edge_count(), 0);
+
+    add_edges(&ids, &mut g);
+    assert_eq!(g.edge_count(), g.edge_references().count());
+    assert_eq!(g.edge_count(), 5);
+
+    let h = g.clone();
+    *g.edge(ids[0], ids[1]).or_insert(0) += 1;
+    g.edge(ids[1], ids[0]).or_insert(21);
+    g.connect_mut(ids[2], ids[3], 43);
+    g.connect_mut(ids[3], ids[3], 44);
+    assert_eq!(g.edge_count(), g.edge_references().count());
+    assert_eq!(h.edge_count(), h.edge_references().count());
+
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[2], ids[1]);
+    assert_eq!(g.edge_count(), g.edge_references().count());
+
+    g.remove_mut(ids[1]);
+    assert_eq!(g.edge_count(), g.edge_references().count());
+    assert_eq!(g.edge_count(), 2);
+}
+
+#[test]
+fn test_into_edges_outbound_only() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    for id in &ids {
+        let edges: Vec<_> = IntoEdges::edges(&g, *id).collect();
+        let outbound: Vec<_> = g.outbound_edges(id).collect();
+        assert_eq!(edges, outbound);
+
+        let incoming: Vec<_> = g.edges_directed(*id, Direction::Incoming).collect();
+        let predecessors: Vec<_> = g.predecessors(id).collect();
+        assert_eq!(incoming, predecessors);
+    }
+
+    // ids[1] only has an outbound edge to ids[2], but has incoming edges from ids[0], ids[2], and ids[3].
+    assert_eq!(IntoEdges::edges(&g, ids[1]).count(), 1);
+}
+
+#[test]
+fn test_adjacency_matrix() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[1]);
+    g.connect_mut(ids[0], ids[3], 14);
+    g.connect_mut(ids[3], ids[3], 44);
+
+    let matrix = g.adjacency_matrix();
+    assert_eq!(matrix.len(), g.edge_count());
+
+    let live: Vec<_> = g.ids().collect();
+    for &source in &live {
+        for &sink in &live {
+            assert_eq!(
+                g.is_adjacent(&matrix, source, sink),
+                g.has_edge(source, sink)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_compact_index_round_trip() {
+    let size: usize = 8;
+
+    // Try every possible set of removed vertices, so we cover holes at the start, middle, and end.
+    for removed in 0..(1 << size) {
+        let mut g = PGraph::<usize, ()>::new();
+        let ids: Vec<_> = g.add_all_mut(0..size);
+        for (i, id) in ids.iter().enumerate() {
+            if removed & (1 << i) != 0 {
+                g.remove_mut(id);
+            }
+        }
+
+        assert_eq!(g.node_count(), g.ids().count());
+        assert_eq!(g.node_bound(), g.node_count());
+
+        let indices: Vec<_> = g.ids().map(|id| g.to_index(id)).collect();
+        let expected: Vec<_> = (0..g.node_count()).collect();
+        assert_eq!(indices, expected);
+
+        for id in g.ids() {
+            assert_eq!(g.from_index(g.to_index(id)), id);
+        }
+        for i in 0..g.node_bound() {
+            assert_eq!(g.to_index(g.from_index(i)), i);
+        }
+    }
+}
+
+#[test]
+fn test_compact_index_after_readd() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    g.remove_mut(ids[1]);
+    g.remove_mut(ids[2]);
+    let id5 = g.add_mut(5);
+    let id6 = g.add_mut(6);
+    g.connect_mut(id5, id6, 56);
+
+    assert_ne!(id5, id6);
+    assert_eq!(g.node_count(), 4);
+    for id in g.ids() {
+        assert_eq!(g.from_index(g.to_index(id)), id);
+    }
+
+    // `connected_components` relies on `NodeCompactIndexable`
+    assert_eq!(petgraph::algo::connected_components(&g), 3);
+}
+
+#[test]
+fn test_from_petgraph() {
+    let mut pet = petgraph::Graph::<usize, usize>::new();
+    let nodes: Vec<_> = (1..=4).map(|i| pet.add_node(i)).collect();
+    pet.add_edge(nodes[0], nodes[1], 12);
+    pet.add_edge(nodes[1], nodes[2], 23);
+    pet.add_edge(nodes[2], nodes[1], 32);
+    pet.add_edge(nodes[2], nodes[3], 0);
+    pet.add_edge(nodes[2], nodes[3], 34);
+
+    let (g, ids) = PGraph::from_petgraph(&pet);
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 4);
+
+    for node in &nodes {
+        assert_eq!(g[(ids[node],)], pet[*node]);
+    }
+    for edge in pet.edge_indices() {
+        let (source, sink) = pet.edge_endpoints(edge).unwrap();
+        assert!(g.has_edge(ids[&source], ids[&sink]));
+    }
+    assert_eq!(g[(ids[&nodes[2]], ids[&nodes[3]])], 34);
+    assert!(!g.has_edge(ids[&nodes[1]], ids[&nodes[0]]));
+}
+
+/// Reverses every edge of `g` by hand, keeping the same vertices (and Ids).
+fn reverse_by_hand(g: &PGraph<usize, usize>) -> PGraph<usize, usize> {
+    let mut reversed = g.clone();
+    for (source, sink, _) in g.edges() {
+        reversed.disconnect_mut(source, sink);
+    }
+    for (source, sink, weight) in g.edges() {
+        reversed.connect_mut(sink, source, *weight);
+    }
+    reversed
+}
+
+#[test]
+fn test_reversed_matches_reverse_by_hand() {
+    use petgraph::algo::{dijkstra, has_path_connecting, toposort};
+    use petgraph::visit::{Bfs, EdgeRef, IntoNeighborsDirected};
+    use std::collections::HashSet;
+
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
+    g.connect_mut(ids[0], ids[3], 14);
+    let by_hand = reverse_by_hand(&g);
+    let reversed = Reversed(&g);
+
+    for &id in &ids {
+        for &dir in &[Direction::Outgoing, Direction::Incoming] {
+            let expected: HashSet<_> = (&by_hand).neighbors_directed(id, dir).collect();
+            let actual: HashSet<_> = reversed.neighbors_directed(id, dir).collect();
+            assert_eq!(actual, expected);
+        }
+
+        let mut expected = HashSet::new();
+        let mut bfs = Bfs::new(&by_hand, id);
+        while let Some(visited) = bfs.next(&by_hand) {
+            expected.insert(visited);
+        }
+        let mut actual = HashSet::new();
+        let mut bfs = Bfs::new(reversed, id);
+        while let Some(visited) = bfs.next(reversed) {
+            actual.insert(visited);
+        }
+        assert_eq!(actual, expected);
+
+        for &other in &ids {
+            assert_eq!(
+                has_path_connecting(reversed, id, other, None),
+                has_path_connecting(&by_hand, id, other, None)
+            );
+        }
+    }
+
+    let expected: HashSet<_> = by_hand
+        .edges()
+        .map(|(source, sink, weight)| (source, sink, *weight))
+        .collect();
+    let actual: HashSet<_> = reversed
+        .edge_references()
+        .map(|edge| (edge.source(), edge.target(), *edge.weight()))
+        .collect();
+    assert_eq!(actual, expected);
+
+    // petgraph 0.4's `Reversed` doesn't implement `IntoEdges`, so `dijkstra` can't run on it directly. Check the
+    // hand-reversed PGraph against petgraph's own `Graph::reverse` instead.
+    let mut pet = petgraph::Graph::<usize, usize>::new();
+    let nodes: Vec<_> = ids.iter().map(|&id| pet.add_node(g[(id,)])).collect();
+    for (source, sink, weight) in g.edges() {
+        let index = |id| ids.iter().position(|&i| i == id).unwrap();
+        pet.add_edge(nodes[index(source)], nodes[index(sink)], *weight);
+    }
+    pet.reverse();
+    for (i, &id) in ids.iter().enumerate() {
+        let expected = dijkstra(&pet, nodes[i], None, |edge| *edge.weight());
+        let actual = dijkstra(&by_hand, id, None, |(_, _, weight)| *weight);
+        assert_eq!(actual.len(), expected.len());
+        for (j, &other) in ids.iter().enumerate() {
+            assert_eq!(actual.get(&other), expected.get(&nodes[j]));
+        }
+    }
+
+    // Without the cycle between 1 and 2, the reversed topological order is the original one backwards.
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[3], ids[1]);
+    g.disconnect_mut(ids[3], ids[3]);
+    let mut order = toposort(&g, None).unwrap();
+    order.reverse();
+    assert_eq!(toposort(Reversed(&g), None).unwrap(), order);
+}
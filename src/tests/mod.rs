@@ -1,6 +1,14 @@
 use super::*;
 
+mod algo;
+mod bytes;
+mod external_impls;
 mod panics;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rand")]
+mod random;
+mod stats;
 
 #[test]
 fn test_add_vertices() {
@@ -190,6 +198,56 @@ fn test_edges() {
     assert!(!b.has_edge(b_ids[2], b_ids[3]));
 }
 
+#[test]
+fn test_for_each_weight_mut() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let h = g.clone();
+
+    let pairs = vec![
+        (ids[1], ids[2]),
+        (ids[2], ids[1]),
+        (ids[0], ids[3]),
+        (b_ids[1], ids[2]),
+    ];
+    let mut seen = Vec::new();
+    let count = g.for_each_weight_mut(pairs, |source, sink, weight| {
+        seen.push((source, sink));
+        *weight += 100;
+    });
+
+    assert_eq!(count, 2);
+    assert_eq!(seen, vec![(ids[1], ids[2]), (ids[2], ids[1])]);
+    assert_eq!(g[(ids[1], ids[2])], 123);
+    assert_eq!(g[(ids[2], ids[1])], 132);
+    assert_eq!(h[(ids[1], ids[2])], 23);
+    assert_eq!(g[(ids[0], ids[1])], 12);
+}
+
+#[test]
+fn test_self_loops() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    assert_eq!(g.self_loops().count(), 0);
+
+    g.connect_mut(ids[1], ids[1], 22);
+    g.connect_mut(ids[3], ids[3], 44);
+    assert!(g.has_self_loop(ids[1]));
+    assert!(!g.has_self_loop(ids[2]));
+    assert!(!g.has_self_loop(b_ids[1]));
+
+    let loops: Vec<_> = g.self_loops().collect();
+    assert_eq!(loops, vec![(ids[1], &22), (ids[3], &44)]);
+
+    let h = g.clone();
+    assert_eq!(g.remove_self_loops_mut(), 2);
+    assert_eq!(g.self_loops().count(), 0);
+    assert_eq!(g.edge_count(), 5);
+    assert_eq!(h.self_loops().count(), 2);
+}
+
 #[test]
 fn test_remove_all() {
     let (a_ids, mut a) = create_vertices();
@@ -227,29 +285,2004 @@ fn test_add_all() {
 }
 
 #[test]
-fn test_debug() {
-    let (a_ids, mut a) = create_vertices();
-    add_edges(&a_ids, &mut a);
-    let before_gen = a.generation();
-    a.remove_mut(a_ids[0]);
-    a.disconnect_mut(a_ids[3], a_ids[1]);
-    let after_gen = a.generation();
+fn test_neighbors_undirected() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
 
-    let result = format!("{:?}", a);
-    let expected = format!(
-        "{}\n{}\n{}\n{}\n{}\n{}\n",
-        format!("PGraph (gen {}) {{", after_gen),
-        format!("\tBlank"),
-        format!("\t2 (1 gen{}) => (2 gen{}: 23)", before_gen, before_gen),
-        format!(
-            "\t3 (2 gen{}) => (1 gen{}: 32, 3 gen{}: 34)",
-            before_gen, before_gen, before_gen
-        ),
-        format!("\t4 (3 gen{}) => (None)", before_gen),
-        "}"
+    let neighbors = |id: Id| -> Vec<Id> { g.neighbors_undirected(id).collect() };
+    assert_eq!(neighbors(ids[0]), vec![ids[1]]);
+    assert_eq!(neighbors(ids[1]), vec![ids[2], ids[0], ids[3]]);
+    assert_eq!(neighbors(ids[2]), vec![ids[1], ids[3]]);
+    assert_eq!(neighbors(ids[3]), vec![ids[1], ids[3], ids[2]]);
+    assert!(neighbors(b_ids[0]).is_empty());
+}
+
+#[test]
+fn test_pgraph_macro() {
+    let (ids, mut expected) = create_vertices();
+    add_edges(&ids, &mut expected);
+
+    pgraph! {
+        g: PGraph<usize, usize>;
+        a: 1, b: 2, c: 3, d: 4;
+        a -> b: 12, b -> c: 23, c -> b: 32, c -> d: 34, d -> b: 42,
+    }
+
+    let new_ids = [a, b, c, d];
+    assert_eq!(g.ids().count(), expected.ids().count());
+    assert_eq!(g.edge_count(), expected.edge_count());
+    for (id, expected_id) in new_ids.iter().zip(ids.iter()) {
+        assert_eq!(g[(id,)], expected[(expected_id,)]);
+        for (sink, expected_sink) in new_ids.iter().zip(ids.iter()) {
+            assert_eq!(
+                g.weight(id, sink),
+                expected.weight(expected_id, expected_sink)
+            );
+        }
+    }
+
+    pgraph! { mut empty: PGraph<usize, usize>; ; }
+    assert_eq!(empty.ids().count(), 0);
+    let e = empty.add_mut(5);
+    assert_eq!(empty[(e,)], 5);
+}
+
+#[test]
+fn test_removed_ids_never_alias() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    // Every removal path frees slots 1 and 2, then refills them with new vertices.
+    let mut results = Vec::new();
+
+    let mut h = g.clone();
+    h.remove_all_mut(&ids[1..3]);
+    let added: Vec<Id> = h.add_all_mut(vec![5usize, 6]);
+    results.push((h, added));
+
+    let mut h = g.clone();
+    h.remove_mut(ids[1]);
+    h.remove_mut(ids[2]);
+    let added: Vec<Id> = h.add_all_mut(vec![5usize, 6]);
+    results.push((h, added));
+
+    let (h, added) = g.remove_all(&ids[1..3]).add_all(vec![5usize, 6]);
+    results.push((h, added));
+
+    let (h, added) = g
+        .try_remove_all(&ids[1..3])
+        .unwrap()
+        .add_all(vec![5usize, 6]);
+    results.push((h, added));
+
+    let (h, added) = g.remove_then_add(&ids[1..3], vec![5usize, 6]);
+    results.push((h, added));
+
+    let added: Vec<Id> = g.remove_then_add_mut(&ids[1..3], vec![5usize, 6]);
+    results.push((g, added));
+
+    for (h, added) in results {
+        assert_eq!(h.ids().count(), 4);
+        for removed in &ids[1..3] {
+            assert!(!h.has_vertex(removed));
+            assert!(!added.contains(removed));
+        }
+        assert_eq!(h[(added[0],)], 5);
+        assert_eq!(h[(added[1],)], 6);
+        assert!(!h.has_edge(ids[0], added[0]));
+        assert!(!h.has_edge(added[0], added[1]));
+    }
+}
+
+#[test]
+fn test_vertex_data_pair_mut() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    let h = g.clone();
+
+    {
+        let (a, b) = g.vertex_data_pair_mut(ids[0], ids[3]).unwrap();
+        std::mem::swap(a, b);
+        *b += 10;
+    }
+    assert_eq!(g[(ids[0],)], 4);
+    assert_eq!(g[(ids[3],)], 11);
+    assert_eq!(h[(ids[0],)], 1);
+    assert_eq!(h[(ids[3],)], 4);
+
+    {
+        let (a, b) = g.vertex_data_pair_mut(ids[2], ids[1]).unwrap();
+        *a += 100;
+        *b += 200;
+    }
+    assert_eq!(g[(ids[2],)], 103);
+    assert_eq!(g[(ids[1],)], 202);
+
+    assert!(g.vertex_data_pair_mut(ids[1], ids[1]).is_none());
+    assert!(g.vertex_data_pair_mut(ids[1], b_ids[2]).is_none());
+    assert!(g.vertex_data_pair_mut(b_ids[1], ids[2]).is_none());
+}
+
+#[test]
+fn test_predecessors_mut() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let h = g.clone();
+
+    let mut sources: Vec<Id> = Vec::new();
+    for (source, weight) in g.predecessors_mut(ids[1]) {
+        sources.push(source);
+        *weight *= 10;
+    }
+    assert_eq!(sources, vec![ids[0], ids[2], ids[3]]);
+
+    assert_eq!(g.weight(ids[0], ids[1]), Some(&120));
+    assert_eq!(g.weight(ids[2], ids[1]), Some(&320));
+    assert_eq!(g.weight(ids[3], ids[1]), Some(&420));
+    assert_eq!(g.weight(ids[1], ids[2]), Some(&23));
+    assert_eq!(g.weight(ids[2], ids[3]), Some(&34));
+    assert_eq!(h.weight(ids[0], ids[1]), Some(&12));
+
+    assert_eq!(g.predecessors_mut(ids[0]).count(), 0);
+    assert_eq!(g.predecessors_mut(b_ids[1]).count(), 0);
+
+    let mut r = PGraph::<usize, usize>::with_reverse_index();
+    let r_ids: Vec<Id> = r.add_all_mut(vec![1usize, 2, 3, 4]);
+    add_edges(&r_ids, &mut r);
+    for (_, weight) in r.predecessors_mut(r_ids[1]) {
+        *weight += 1;
+    }
+    assert_eq!(r.weight(r_ids[3], r_ids[1]), Some(&43));
+    assert_eq!(r.weight(r_ids[2], r_ids[3]), Some(&34));
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    g.remove_mut(ids[1]);
+    g.shrink_to_fit_mut();
+    assert_eq!(g.count_slots(), 4);
+    assert_eq!(g.count_empties(), 1);
+
+    g.remove_mut(ids[3]);
+    g.shrink_to_fit_mut();
+    assert_eq!(g.count_slots(), 3);
+    assert_eq!(g.count_empties(), 1);
+
+    g.remove_mut(ids[2]);
+    g.shrink_to_fit_mut();
+    assert_eq!(g.count_slots(), 1);
+    assert_eq!(g.count_empties(), 0);
+    assert_eq!(g[(ids[0],)], 1);
+
+    let id = g.add_mut(5);
+    assert_eq!(g.count_slots(), 2);
+    assert!(!ids.contains(&id));
+    assert_eq!(g.ids().collect::<Vec<_>>(), vec![ids[0], id]);
+
+    g.remove_all_mut(vec![ids[0], id]);
+    g.shrink_to_fit_mut();
+    assert_eq!(g.count_slots(), 0);
+    assert_eq!(g.count_empties(), 0);
+}
+
+#[test]
+fn test_connect_checked() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+
+    assert_eq!(g.connect_checked(ids[0], ids[1], 12), Ok(()));
+    assert_eq!(g.connect_checked(ids[0], ids[1], 120), Ok(()));
+    assert_eq!(g.weight(ids[0], ids[1]), Some(&120));
+
+    assert_eq!(
+        g.connect_checked(b_ids[0], ids[1], 0),
+        Err(ConnectError::SourceMissing(b_ids[0]))
+    );
+    assert_eq!(
+        g.connect_checked(ids[0], b_ids[1], 0),
+        Err(ConnectError::SinkMissing(b_ids[1]))
+    );
+    assert_eq!(
+        g.connect_checked(b_ids[0], b_ids[1], 0),
+        Err(ConnectError::SourceMissing(b_ids[0]))
     );
+    assert_eq!(g.edge_count(), 1);
 
-    assert_eq!(result, expected);
+    g.remove_mut(ids[2]);
+    assert_eq!(
+        g.connect_checked(ids[3], ids[2], 0),
+        Err(ConnectError::SinkMissing(ids[2]))
+    );
+}
+
+#[test]
+fn test_weights_many_mut() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let h = g.clone();
+
+    {
+        let [a, b, c] = g
+            .weights_many_mut([(ids[1], ids[2]), (ids[2], ids[1]), (ids[3], ids[1])])
+            .unwrap();
+        *a += 100;
+        *b += 200;
+        *c += 300;
+    }
+    assert_eq!(g.weight(ids[1], ids[2]), Some(&123));
+    assert_eq!(g.weight(ids[2], ids[1]), Some(&232));
+    assert_eq!(g.weight(ids[3], ids[1]), Some(&342));
+    assert_eq!(g.weight(ids[0], ids[1]), Some(&12));
+    assert_eq!(h.weight(ids[1], ids[2]), Some(&23));
+
+    {
+        let [a, b, c] = g
+            .weights_many_mut([(ids[2], ids[3]), (ids[0], ids[1]), (ids[2], ids[1])])
+            .unwrap();
+        *a += 1000;
+        *b += 2000;
+        *c += 3000;
+    }
+    assert_eq!(g.weight(ids[2], ids[3]), Some(&1034));
+    assert_eq!(g.weight(ids[0], ids[1]), Some(&2012));
+    assert_eq!(g.weight(ids[2], ids[1]), Some(&3232));
+    assert_eq!(h.weight(ids[2], ids[3]), Some(&34));
+
+    let none: [(Id, Id); 0] = [];
+    assert!(g.weights_many_mut(none).is_some());
+    assert!(g
+        .weights_many_mut([(ids[1], ids[2]), (ids[1], ids[2])])
+        .is_none());
+    assert!(g
+        .weights_many_mut([(ids[0], ids[1]), (ids[1], ids[0])])
+        .is_none());
+    assert!(g
+        .weights_many_mut([(ids[0], ids[1]), (b_ids[1], ids[2])])
+        .is_none());
+}
+
+#[test]
+fn test_generation_id() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    let gen = g.current_generation();
+
+    for id in &ids {
+        assert_eq!(id.generation_id(), gen);
+        assert_ne!(b_ids[0].generation_id(), gen);
+    }
+
+    g.connect_mut(ids[0], ids[1], 12);
+    assert_eq!(g.current_generation(), gen);
+
+    g.remove_mut(ids[3]);
+    assert_ne!(g.current_generation(), gen);
+    let id = g.add_mut(5);
+    assert_eq!(id.generation_id(), g.current_generation());
+    assert_ne!(id.generation_id(), ids[3].generation_id());
+}
+
+#[test]
+fn test_add_at() {
+    let (ids, g) = create_vertices();
+    let mut h: PGraph<usize, usize> = PGraph::new();
+
+    // Rebuild `g` in reverse order, so every vertex has to be placed past the end of `h`.
+    for id in ids.iter().rev() {
+        let new_id = h.add_at_mut(id.index(), g[(id,)]).unwrap();
+        assert_eq!(new_id.index(), id.index());
+        assert_ne!(new_id, *id);
+    }
+    assert_eq!(h.count_slots(), 4);
+    assert_eq!(h.count_empties(), 0);
+
+    let occupant = h.ids().nth(2).unwrap();
+    assert_eq!(h.add_at_mut(2, 5), Err(OccupiedError { occupant }));
+
+    let id = h.add_at_mut(7, 8).unwrap();
+    assert_eq!(h.count_slots(), 8);
+    assert_eq!(h.count_empties(), 3);
+    assert_eq!(h.add_mut(5).index(), 4);
+    assert_eq!(h[(id,)], 8);
+    assert_eq!(h.ids().count(), 6);
+}
+
+#[test]
+fn test_edges_between() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
+
+    assert_eq!(g.edges_between(ids[0], ids[1]), (Some(&12), None));
+    assert_eq!(g.edges_between(ids[1], ids[0]), (None, Some(&12)));
+    assert_eq!(g.edges_between(ids[1], ids[2]), (Some(&23), Some(&32)));
+    assert_eq!(g.edges_between(ids[0], ids[3]), (None, None));
+    assert_eq!(g.edges_between(ids[3], ids[3]), (Some(&44), Some(&44)));
+    assert_eq!(g.edges_between(ids[1], ids[1]), (None, None));
+    assert_eq!(g.edges_between(ids[1], b_ids[2]), (None, None));
+
+    g.remove_mut(ids[2]);
+    assert_eq!(g.edges_between(ids[1], ids[2]), (None, None));
+}
+
+#[test]
+fn test_canonicalize() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    // Build the same graph with extra vertices that get removed, leaving holes in the layout.
+    let mut h = PGraph::new();
+    let extra = h.add_mut(0);
+    let mut h_ids: Vec<Id> = h.add_all_mut(vec![1usize, 2]);
+    h.remove_mut(extra);
+    let extra = h.add_mut(0);
+    h_ids.extend(h.add_all_mut::<Vec<_>, _, _>(vec![3usize, 4]));
+    h.remove_mut(extra);
+    add_edges(&h_ids, &mut h);
+    assert_eq!(h.count_empties(), 1);
+
+    let (g_canon, g_map) = g.canonicalize();
+    let (h_canon, h_map) = h.canonicalize();
+    assert_eq!(g_map.len(), 4);
+    assert_eq!(h_map.len(), 4);
+    assert_eq!(h_canon.count_slots(), 4);
+    assert_eq!(h_canon.count_empties(), 0);
+
+    for (g_id, h_id) in g_canon.ids().zip(h_canon.ids()) {
+        assert_eq!(g_id.index(), h_id.index());
+        assert_eq!(g_canon[(g_id,)], h_canon[(h_id,)]);
+        for (g_sink, h_sink) in g_canon.ids().zip(h_canon.ids()) {
+            assert_eq!(g_canon.weight(g_id, g_sink), h_canon.weight(h_id, h_sink));
+        }
+    }
+    for (old, new) in ids.iter().zip(h_ids.iter()) {
+        assert_eq!(g_map[old].index(), h_map[new].index());
+    }
+}
+
+#[test]
+fn test_reverse_index() {
+    let (ids, mut scanned) = create_vertices();
+    add_edges(&ids, &mut scanned);
+    let mut indexed = PGraph::with_reverse_index();
+    let indexed_ids: Vec<Id> = indexed.add_all_mut(1usize..=4);
+    add_edges(&indexed_ids, &mut indexed);
+    assert!(!scanned.has_reverse_index());
+    assert!(indexed.has_reverse_index());
+
+    let assert_same = |scanned: &PGraph<usize, usize>, indexed: &PGraph<usize, usize>| {
+        assert_eq!(scanned.ids().count(), indexed.ids().count());
+        for (id, indexed_id) in scanned.ids().zip(indexed.ids()) {
+            let preds: Vec<_> = scanned
+                .predecessors(id)
+                .map(|(s, _, w)| (s.index(), *w))
+                .collect();
+            let indexed_preds: Vec<_> = indexed
+                .predecessors(indexed_id)
+                .map(|(s, _, w)| (s.index(), *w))
+                .collect();
+            assert_eq!(preds, indexed_preds);
+            assert_eq!(scanned.in_degree(id), indexed.in_degree(indexed_id));
+        }
+    };
+    assert_same(&scanned, &indexed);
+    assert_eq!(indexed.in_degree(indexed_ids[1]), 3);
+
+    // Each kind of edge creation and removal has to keep the index in sync.
+    for (g, ids) in [(&mut scanned, &ids), (&mut indexed, &indexed_ids)] {
+        g.disconnect_mut(ids[2], ids[1]);
+        g.try_disconnect_mut(ids[0], ids[1]);
+        assert!(g.try_connect_mut(ids[0], ids[3], 14));
+        assert_eq!(g.connect_checked(ids[3], ids[3], 44), Ok(()));
+        *g.edge(ids[1], ids[0]).or_default() += 21;
+        g[ids[3]].disconnect(ids[1]);
+    }
+    assert_same(&scanned, &indexed);
+    assert_eq!(indexed.in_degree(indexed_ids[1]), 0);
+    assert_eq!(indexed.in_degree(indexed_ids[3]), 3);
+
+    let scanned_clone = scanned.clone();
+    let indexed_clone = indexed.clone();
+    scanned.remove_mut(ids[3]);
+    indexed.remove_mut(indexed_ids[3]);
+    assert_same(&scanned, &indexed);
+    assert_same(&scanned_clone, &indexed_clone);
+
+    // A vertex reusing a removed vertex's slot starts with no predecessors.
+    let new_id = indexed.add_mut(5);
+    assert_eq!(new_id.index(), indexed_ids[3].index());
+    assert_eq!(indexed.in_degree(new_id), 0);
+    assert_eq!(indexed.in_degree(indexed_ids[2]), 1);
+
+    assert!(indexed.recreate().has_reverse_index());
+    assert!(indexed.complement().has_reverse_index());
+}
+
+#[test]
+fn test_weight_or_default_mut() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    *g.weight_or_default_mut(ids[0], ids[1]).unwrap() += 100;
+    assert_eq!(g.weight(ids[0], ids[1]), Some(&112));
+
+    assert_eq!(g.weight_or_default_mut(ids[3], ids[0]), Some(&mut 0));
+    assert_eq!(g.weight(ids[3], ids[0]), Some(&0));
+    assert_eq!(g.edge_count(), 6);
+
+    assert!(g.weight_or_default_mut(ids[0], b_ids[1]).is_none());
+    assert!(g.weight_or_default_mut(b_ids[0], ids[1]).is_none());
+    g.remove_mut(ids[2]);
+    assert!(g.weight_or_default_mut(ids[1], ids[2]).is_none());
+    assert_eq!(g.edge_count(), 3);
+}
+
+#[test]
+fn test_isolated_vertices() {
+    let (ids, mut g) = create_vertices();
+    assert_eq!(g.isolated_vertices().collect::<Vec<_>>(), ids);
+
+    add_edges(&ids, &mut g);
+    assert_eq!(g.isolated_vertices().count(), 0);
+
+    // Vertices with only incoming or only outgoing edges aren't isolated.
+    g.disconnect_mut(ids[1], ids[2]);
+    g.disconnect_mut(ids[3], ids[1]);
+    assert_eq!(g.isolated_vertices().count(), 0);
+
+    g.disconnect_mut(ids[2], ids[3]);
+    assert_eq!(g.isolated_vertices().collect::<Vec<_>>(), vec![ids[3]]);
+
+    let gen = g.generation();
+    let h = g.clone();
+    assert_eq!(g.remove_isolated_mut(), 1);
+    assert_ne!(g.generation(), gen);
+    assert_eq!(g.ids().count(), 3);
+    assert!(h.has_vertex(ids[3]));
+
+    let gen = g.generation();
+    assert_eq!(g.remove_isolated_mut(), 0);
+    assert_eq!(g.generation(), gen);
+}
+
+#[test]
+fn test_upsert_edge() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let h = g.clone();
+
+    assert_eq!(g.upsert_edge_mut(ids[0], ids[1], 12), EdgeUpsert::Unchanged);
+    assert_eq!(
+        g.upsert_edge_mut(ids[0], ids[1], 21),
+        EdgeUpsert::Updated(12)
+    );
+    assert_eq!(g.upsert_edge_mut(ids[1], ids[0], 21), EdgeUpsert::Created);
+    assert_eq!(g.upsert_edge_mut(ids[1], ids[0], 21), EdgeUpsert::Unchanged);
+    assert_eq!(g.weight(ids[0], ids[1]), Some(&21));
+    assert_eq!(h.weight(ids[0], ids[1]), Some(&12));
+    assert!(!h.has_edge(ids[1], ids[0]));
+
+    assert!(!g.insert_edge_mut(ids[2], ids[3], 43));
+    assert!(g.insert_edge_mut(ids[3], ids[2], 43));
+    assert_eq!(g.weight(ids[2], ids[3]), Some(&43));
+    assert_eq!(g.edge_count(), 7);
+}
+
+#[test]
+fn test_swap_vertex_data() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let h = g.clone();
+
+    assert!(g.swap_vertex_data(ids[0], ids[3]));
+    assert_eq!(g[(ids[0],)], 4);
+    assert_eq!(g[(ids[3],)], 1);
+    assert_eq!(h[(ids[0],)], 1);
+    assert_eq!(h[(ids[3],)], 4);
+    assert_eq!(g.ids().collect::<Vec<_>>(), ids);
+    for &source in &ids {
+        for &sink in &ids {
+            assert_eq!(g.weight(source, sink), h.weight(source, sink));
+        }
+    }
+
+    assert!(!g.swap_vertex_data(ids[1], ids[1]));
+    assert!(!g.swap_vertex_data(ids[1], b_ids[2]));
+    assert!(!g.swap_vertex_data(b_ids[1], ids[2]));
+    assert_eq!(g[(ids[1],)], 2);
+    assert_eq!(g[(ids[2],)], 3);
+}
+
+#[test]
+fn test_vertex_data_all() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    g.remove_mut(ids[1]);
+
+    let lookup = vec![ids[3], ids[1], b_ids[0], ids[0], ids[3]];
+    assert_eq!(
+        g.vertex_data_all(&lookup),
+        vec![Some(&4), None, None, Some(&1), Some(&4)]
+    );
+    assert_eq!(
+        g.vertex_data_all(lookup.into_iter().take(1)),
+        vec![Some(&4)]
+    );
+    assert!(g.vertex_data_all(Vec::<Id>::new()).is_empty());
+}
+
+#[test]
+fn test_fold_neighbors() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let sinks = |id: Id| {
+        g.fold_neighbors(id, Vec::new(), |mut acc, sink, weight| {
+            acc.push((sink, *weight));
+            acc
+        })
+    };
+    assert_eq!(sinks(ids[0]), vec![(ids[1], 12)]);
+    assert_eq!(sinks(ids[2]), vec![(ids[1], 32), (ids[3], 34)]);
+    assert_eq!(sinks(b_ids[2]), vec![]);
+
+    for id in &ids {
+        let expected: usize = g.outbound_edges(id).map(|(_, _, w)| w).sum();
+        assert_eq!(g.fold_neighbors(id, 0, |acc, _, w| acc + w), expected);
+    }
+    assert_eq!(g.fold_neighbors(b_ids[0], 7, |acc, _, w| acc + w), 7);
+}
+
+#[test]
+fn test_iteration_order() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let in_order = |g: &PGraph<usize, usize>| {
+        let ids: Vec<Id> = g.ids().collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+        assert_eq!(g.into_iter().map(Vertex::id).collect::<Vec<_>>(), ids);
+        let data: Vec<usize> = ids.iter().map(|id| g[(id,)]).collect();
+        assert_eq!(g.iter_data().cloned().collect::<Vec<_>>(), data);
+
+        let edges: Vec<(Id, Id)> = g.edges().map(|(source, sink, _)| (source, sink)).collect();
+        let mut sorted = edges.clone();
+        sorted.sort();
+        assert_eq!(edges, sorted);
+        ids
+    };
+    assert_eq!(in_order(&g), ids);
+    assert_eq!(g.iter_data().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+    // New vertices fill the lowest empty slot, so they can come before older vertices.
+    g.remove_all_mut(&ids[0..2]);
+    let id5 = g.add_mut(5);
+    g.connect_mut(ids[3], id5, 45);
+    assert_eq!(in_order(&g), vec![id5, ids[2], ids[3]]);
+    assert_eq!(g.iter_data().cloned().collect::<Vec<_>>(), vec![5, 3, 4]);
+
+    let (h, id_map) = g.canonicalize();
+    let expected: Vec<Id> = in_order(&g).iter().map(|id| id_map[id]).collect();
+    assert_eq!(in_order(&h), expected);
+    assert_eq!(in_order(&g.recreate()).len(), 3);
+}
+
+#[test]
+fn test_remove_all_reporting() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let gen = g.generation();
+    let report = g.remove_all_reporting_mut(vec![b_ids[0], b_ids[1]]);
+    assert_eq!(report, vec![(b_ids[0], false), (b_ids[1], false)]);
+    assert_eq!(g.generation(), gen);
+
+    let report = g.remove_all_reporting_mut(vec![ids[1], b_ids[2], ids[3], ids[1]]);
+    assert_eq!(
+        report,
+        vec![
+            (ids[1], true),
+            (b_ids[2], false),
+            (ids[3], true),
+            (ids[1], false)
+        ]
+    );
+    assert_ne!(g.generation(), gen);
+    assert_eq!(g.ids().collect::<Vec<_>>(), vec![ids[0], ids[2]]);
+    assert_eq!(g.edge_count(), 0);
+
+    let new_ids: Vec<Id> = g.add_all_mut(vec![5usize, 6]);
+    assert!(!new_ids.contains(&ids[1]));
+    assert!(!new_ids.contains(&ids[3]));
+}
+
+#[test]
+fn test_reserve_neighbors() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let more: Vec<Id> = g.add_all_mut(5usize..=10);
+    let h = g.clone();
+    assert_eq!(g[ids[0]].neighbor_slots(), 2);
+
+    g.reserve_neighbors(ids[0], 9);
+    assert_eq!(g[ids[0]].neighbor_slots(), 10);
+    assert_eq!(h[ids[0]].neighbor_slots(), 2);
+    assert_eq!(g[ids[0]].len_neighbors(), 1);
+    assert_eq!(g.edge_count(), h.edge_count());
+
+    // Reserving less than the current size does nothing.
+    g.reserve_neighbors(ids[0], 3);
+    assert_eq!(g[ids[0]].neighbor_slots(), 10);
+
+    // Reserving past the last slot stops at it.
+    g.reserve_neighbors(ids[1], usize::MAX);
+    assert_eq!(g[ids[1]].neighbor_slots(), 10);
+
+    for sink in &more {
+        g.connect_mut(ids[0], *sink, 1);
+    }
+    assert_eq!(g[ids[0]].neighbor_slots(), 10);
+    assert_eq!(g[ids[0]].len_neighbors(), 7);
+}
+
+#[test]
+fn test_validate() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    assert_eq!(g.validate(), Ok(()));
+
+    let mut indexed = PGraph::with_reverse_index();
+    let indexed_ids: Vec<Id> = indexed.add_all_mut(1usize..=4);
+    add_edges(&indexed_ids, &mut indexed);
+    assert_eq!(indexed.validate(), Ok(()));
+
+    for g in &mut [g, indexed] {
+        let ids: Vec<Id> = g.ids().collect();
+        g.remove_mut(ids[2]);
+        g.disconnect_mut(ids[3], ids[1]);
+        *g.edge(ids[3], ids[0]).or_default() += 1;
+        g.add_at_mut(6, 7).unwrap();
+        g.remove_isolated_mut();
+        g.shrink_to_fit_mut();
+        assert_eq!(g.validate(), Ok(()));
+        assert_eq!(g.canonicalize().0.validate(), Ok(()));
+        assert_eq!(g.complement().validate(), Ok(()));
+    }
+}
+
+#[test]
+fn test_adjacent() {
+    use petgraph::Direction;
+
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let outgoing: Vec<_> = g.adjacent(ids[2], Direction::Outgoing).collect();
+    assert_eq!(outgoing, vec![(ids[1], &32), (ids[3], &34)]);
+    let incoming: Vec<_> = g.adjacent(ids[1], Direction::Incoming).collect();
+    assert_eq!(incoming, vec![(ids[0], &12), (ids[2], &32), (ids[3], &42)]);
+
+    assert_eq!(g.adjacent(ids[0], Direction::Incoming).count(), 0);
+    assert_eq!(g.adjacent(b_ids[1], Direction::Outgoing).count(), 0);
+    assert_eq!(g.adjacent(b_ids[1], Direction::Incoming).count(), 0);
+}
+
+#[test]
+fn test_try_edge() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    *g.try_edge(ids[0], ids[1]).unwrap().or_insert(0) += 1;
+    assert_eq!(g[(ids[0], ids[1])], 13);
+    *g.try_edge(ids[1], ids[0]).unwrap().or_insert(21) += 1;
+    assert_eq!(g[(ids[1], ids[0])], 22);
+
+    assert!(g.try_edge(ids[0], b_ids[1]).is_none());
+    assert!(g.try_edge(b_ids[0], ids[1]).is_none());
+    g.remove_mut(ids[3]);
+    assert!(g.try_edge(ids[2], ids[3]).is_none());
+    assert_eq!(g.edge_count(), 4);
+}
+
+#[test]
+fn test_union_find() {
+    let mut g = PGraph::<usize, usize>::with_union_find();
+    let ids: Vec<Id> = g.add_all_mut(vec![0usize, 1, 2, 3, 4]);
+    assert_eq!(g.component_count(), 5);
+
+    g.connect_mut(ids[0], ids[1], 1);
+    g.edge(ids[2], ids[3]).or_insert(23);
+    g.edge(ids[3], ids[4]).and_modify(|w| *w += 1);
+    assert!(g.try_connect_mut(ids[4], ids[4], 44));
+    assert_eq!(g.component_count(), 3);
+    assert!(g.same_component(ids[3], ids[2]));
+    assert!(!g.same_component(ids[3], ids[4]));
+
+    let h = g.clone();
+    g.connect_checked(ids[4], ids[1], 41).unwrap();
+    assert_eq!(g.component_count(), 2);
+    assert!(g.same_component(ids[0], ids[4]));
+    assert_eq!(h.component_count(), 3);
+
+    g.disconnect_mut(ids[4], ids[1]);
+    assert_eq!(g.component_count(), 3);
+    g.remove_mut(ids[0]);
+    assert!(!g.same_component(ids[0], ids[1]));
+    assert_eq!(g.component_count(), 3);
+
+    let id5 = g.add_mut(5);
+    assert_eq!(id5.index(), 0);
+    assert!(!g.same_component(id5, ids[1]));
+    g.add_at_mut(7, 7).unwrap();
+    assert_eq!(g.component_count(), 5);
+
+    let (c, _) = g.canonicalize();
+    assert!(c.has_union_find());
+    assert_eq!(c.component_count(), g.component_count());
+    assert_eq!(g.recreate().component_count(), g.component_count());
+}
+
+#[test]
+fn test_union_find_vertex_disconnect() {
+    let mut g = PGraph::<usize, usize>::with_union_find();
+    let ids: Vec<Id> = g.add_all_mut(vec![0usize, 1, 2, 3]);
+    g.connect_mut(ids[0], ids[1], 1);
+    g.connect_mut(ids[1], ids[2], 12);
+    g.connect_mut(ids[2], ids[3], 23);
+    assert_eq!(g.component_count(), 1);
+
+    assert!(g.vertex_mut(ids[0]).unwrap().disconnect(ids[1]));
+    assert_eq!(g.component_count(), 2);
+    assert!(!g.same_component(ids[0], ids[1]));
+
+    assert!(g[ids[1]].disconnect(ids[2]));
+    assert_eq!(g.component_count(), 3);
+
+    for vertex in g.vertices_mut() {
+        let sinks: Vec<Id> = vertex.into_iter().map(|(sink, _)| sink).collect();
+        for sink in sinks {
+            vertex.disconnect(sink);
+        }
+    }
+    assert_eq!(g.component_count(), 4);
+
+    g.connect_mut(ids[3], ids[0], 30);
+    g[(ids[3], ids[0])] += 1;
+    *g.vertex_data_mut(ids[2]).unwrap() += 1;
+    assert_eq!(g.component_count(), 3);
+    assert!(g.same_component(ids[0], ids[3]));
+}
+
+#[test]
+fn test_from_edges() {
+    let (g, pairs) = PGraph::from_edges(vec![
+        (0usize, 1usize, 12usize),
+        (1, 2, 23),
+        (2, 1, 32),
+        (2, 3, 34),
+        (3, 1, 42),
+        (5, 5, 55),
+        (0, 1, 13),
+    ]);
+    assert_eq!(g.ids().count(), 5);
+    assert_eq!(g.edge_count(), 6);
+    assert_eq!(pairs.len(), 7);
+    assert_eq!(pairs[0], pairs[6]);
+    assert_eq!(pairs[5].0, pairs[5].1);
+
+    for (&(source, sink), expected) in pairs.iter().zip(&[0usize, 1, 2, 2, 3, 5, 0]) {
+        assert_eq!(g[source].data(), expected);
+        assert!(g.has_edge(source, sink));
+    }
+    assert_eq!(g[pairs[0]], 13);
+    assert_eq!(g[pairs[4]], 42);
+
+    let (empty, pairs) = PGraph::<usize, usize>::from_edges(vec![]);
+    assert_eq!(empty.ids().count(), 0);
+    assert!(pairs.is_empty());
+
+    let (g, pairs) = PGraph::from_edges_with(
+        vec![
+            ("a", "b", vec![1usize]),
+            ("b", "a", vec![2]),
+            ("a", "b", vec![3]),
+            ("a", "b", vec![4]),
+        ],
+        |old, new| old.extend(new),
+    );
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g[pairs[0]], vec![1, 3, 4]);
+    assert_eq!(g[pairs[1]], vec![2]);
+}
+
+#[test]
+fn test_as_undirected() {
+    use petgraph::algo::{connected_components, dijkstra};
+    use petgraph::data::DataMap;
+    use petgraph::visit::{IntoEdgeReferences, IntoEdges, IntoNeighbors};
+
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
+    let u = g.as_undirected();
+
+    let neighbors: Vec<_> = u.neighbors(ids[1]).collect();
+    assert_eq!(neighbors, vec![ids[2], ids[0], ids[3]]);
+    let neighbors: Vec<_> = u.neighbors(ids[3]).collect();
+    assert_eq!(neighbors, vec![ids[1], ids[3], ids[2]]);
+
+    // 1 <-> 2 is one undirected edge, and keeps the weight of 1 -> 2 from both ends.
+    assert_eq!(u.edge_references().count(), 5);
+    assert_eq!(u.edge_weight((ids[2], ids[1])), Some(&23));
+    let from_2: Vec<_> = u.edges(ids[2]).collect();
+    assert_eq!(from_2, vec![(ids[2], ids[1], &23), (ids[2], ids[3], &34)]);
+    assert_eq!(u.edge_weight((ids[1], ids[3])), Some(&42));
+    assert_eq!(u.edge_weight((ids[0], ids[3])), None);
+
+    let costs = dijkstra(u, ids[3], None, |(_, _, weight)| *weight);
+    assert_eq!(costs[&ids[0]], 54);
+    assert_eq!(costs[&ids[2]], 34);
+    assert_eq!(connected_components(u), 1);
+
+    g.add_mut(4);
+    assert_eq!(connected_components(g.as_undirected()), 2);
+}
+
+#[test]
+fn test_filtered() {
+    use petgraph::algo::has_path_connecting;
+    use petgraph::data::DataMap;
+    use petgraph::visit::{IntoEdgesDirected, IntoNeighborsDirected, IntoNodeIdentifiers};
+    use petgraph::Direction;
+
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let view = g.filtered(|id, _| id != ids[2]);
+
+    assert!(!view.has_vertex(ids[2]));
+    assert!(!view.has_vertex(b_ids[0]));
+    assert_eq!(view.ids().collect::<Vec<_>>(), vec![ids[0], ids[1], ids[3]]);
+    assert_eq!((&view).node_identifiers().count(), 3);
+    assert_eq!(
+        view.edges().collect::<Vec<_>>(),
+        vec![(ids[0], ids[1], &12), (ids[3], ids[1], &42)]
+    );
+
+    let incoming: Vec<_> = (&view)
+        .neighbors_directed(ids[1], Direction::Incoming)
+        .collect();
+    assert_eq!(incoming, vec![ids[0], ids[3]]);
+    assert_eq!(
+        (&view)
+            .neighbors_directed(ids[2], Direction::Incoming)
+            .count(),
+        0
+    );
+    assert_eq!(
+        (&view).edges_directed(ids[2], Direction::Outgoing).count(),
+        0
+    );
+    assert_eq!(view.node_weight(ids[2]), None);
+    assert_eq!(view.edge_weight((ids[1], ids[2])), None);
+    assert_eq!(view.edge_weight((ids[3], ids[1])), Some(&42));
+
+    assert!(has_path_connecting(&g, ids[1], ids[3], None));
+    assert!(!has_path_connecting(&view, ids[1], ids[3], None));
+}
+
+#[test]
+fn test_disconnect_where() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[2], 33);
+    let before = g.clone();
+
+    assert_eq!(g.disconnect_where_mut(ids[2], |_, weight| *weight < 34), 2);
+    assert_eq!(
+        g.outbound_edges(ids[2]).collect::<Vec<_>>(),
+        vec![(ids[2], ids[3], &34)]
+    );
+    assert_eq!(g.in_degree(ids[1]), 2);
+    assert_eq!(before.outbound_edges(ids[2]).count(), 3);
+
+    assert_eq!(g.disconnect_where_mut(ids[0], |_, _| false), 0);
+    assert_eq!(g.disconnect_where_mut(b_ids[0], |_, _| true), 0);
+    g.remove_mut(ids[3]);
+    assert_eq!(g.disconnect_where_mut(ids[3], |_, _| true), 0);
+    assert_eq!(g.disconnect_where_mut(ids[2], |sink, _| sink == ids[3]), 0);
+    assert_eq!(g.edge_count(), 2);
+}
+
+#[test]
+fn test_connect_add() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let before = g.clone();
+
+    g.connect_add_mut(ids[0], ids[1], 100);
+    g.connect_add_mut(ids[1], ids[0], 21);
+    g.connect_add_mut(ids[1], ids[0], 21);
+    assert_eq!(g[(ids[0], ids[1])], 112);
+    assert_eq!(g[(ids[1], ids[0])], 42);
+    assert_eq!(before[(ids[0], ids[1])], 12);
+    assert_eq!(g.edge_count(), 6);
+
+    g.connect_with_mut(ids[2], ids[3], 1, |old, new| {
+        old.map_or(new, |old| old.max(new))
+    });
+    g.connect_with_mut(ids[3], ids[3], 1, |old, new| {
+        old.map_or(new, |old| old.max(new))
+    });
+    assert_eq!(g[(ids[2], ids[3])], 34);
+    assert_eq!(g[(ids[3], ids[3])], 1);
+    assert_eq!(g.edge_count(), 7);
+
+    let mut v = PGraph::<usize, Vec<u8>>::with_reverse_index();
+    let (a, b) = (v.add_mut(1), v.add_mut(2));
+    v.connect_mut(a, b, vec![1]);
+    let shared = v.clone();
+    let append = |old: Option<Vec<u8>>, new: Vec<u8>| [old.unwrap_or_default(), new].concat();
+    v.connect_with_mut(a, b, vec![2], append);
+    assert_eq!(shared[(a, b)], vec![1]);
+
+    let ptr = v[(a, b)].as_ptr();
+    v.connect_with_mut(a, b, vec![], |old, _| old.unwrap());
+    assert_eq!(v[(a, b)].as_ptr(), ptr);
+    assert_eq!(v[(a, b)], vec![1, 2]);
+    assert_eq!(v.predecessor_ids(b).collect::<Vec<_>>(), vec![a]);
+}
+
+#[test]
+fn test_take_vertex_data() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let shared = g.clone();
+    let gen = g.current_generation();
+
+    assert_eq!(g.take_vertex_data(ids[1]), None);
+    assert!(g.has_vertex(ids[1]));
+    assert_eq!(g.current_generation(), gen);
+    assert_eq!(g.edge_count(), 5);
+    assert_eq!(shared[(ids[1],)], 2);
+    drop(shared);
+
+    assert_eq!(g.take_vertex_data(ids[1]), Some(2));
+    assert!(!g.has_vertex(ids[1]));
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.take_vertex_data(ids[2]), Some(3));
+    assert_eq!(g.take_vertex_data(ids[2]), None);
+    assert_eq!(g.take_vertex_data(b_ids[0]), None);
+    assert_eq!(g.edge_count(), 0);
+
+    let id = g.add_mut(5);
+    assert_ne!(id, ids[1]);
+    assert_eq!(g.take_vertex_data(id), Some(5));
+}
+
+#[test]
+fn test_map() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[0]);
+    let id = g.add_mut(5);
+
+    let m = g.map(
+        |id, data| (id, data.to_string()),
+        |source, sink, weight| (source, sink, *weight as f32 / 2.0),
+    );
+    assert_eq!(m.ids().collect::<Vec<_>>(), g.ids().collect::<Vec<_>>());
+    assert_eq!(m[(id,)], (id, "5".to_string()));
+    assert_eq!(m[(ids[2],)], (ids[2], "3".to_string()));
+    assert!(!m.has_vertex(ids[0]));
+    assert_eq!(m.edge_count(), g.edge_count());
+    for (source, sink, weight) in g.edges() {
+        assert_eq!(m[(source, sink)], (source, sink, *weight as f32 / 2.0));
+    }
+    assert_eq!(m.validate(), Ok(()));
+
+    let mut calls = 0;
+    let _ = g.map(|_, _| (), |_, _, _| calls += 1);
+    assert_eq!(calls, g.edge_count());
+}
+
+#[test]
+fn test_connect_if_absent() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let before = g.clone();
+
+    assert!(!g.connect_if_absent_mut(ids[0], ids[1], 100));
+    assert!(g.connect_if_absent_mut(ids[1], ids[0], 21));
+    assert!(g.connect_if_absent_mut(ids[3], ids[3], 44));
+    assert_eq!(g[(ids[0], ids[1])], 12);
+    assert_eq!(g[(ids[1], ids[0])], 21);
+    assert_eq!(g.edge_count(), 7);
+    assert_eq!(before.edge_count(), 5);
+}
+
+#[test]
+fn test_neighbors_sorted_by() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[0], 34);
+    g.connect_mut(ids[2], ids[2], 1);
+
+    assert_eq!(
+        g.neighbors_sorted_by(ids[2], |&weight| weight),
+        vec![(ids[2], &1), (ids[1], &32), (ids[0], &34), (ids[3], &34)]
+    );
+    assert_eq!(
+        g.neighbors_sorted_by(ids[2], |&weight| std::cmp::Reverse(weight)),
+        vec![(ids[0], &34), (ids[3], &34), (ids[1], &32), (ids[2], &1)]
+    );
+    assert_eq!(
+        g.neighbors_sorted_by(ids[0], |&weight| weight),
+        vec![(ids[1], &12)]
+    );
+    assert!(g.neighbors_sorted_by(b_ids[0], |&weight| weight).is_empty());
+}
+
+#[test]
+fn test_neighbors_cloned() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[2], 33);
+
+    let cloned: Vec<_> = g[ids[2]].neighbors_cloned().collect();
+    assert_eq!(cloned, vec![(ids[1], 32), (ids[2], 33), (ids[3], 34)]);
+    let borrowed: Vec<_> = g[ids[2]].into_iter().map(|(sink, &w)| (sink, w)).collect();
+    assert_eq!(cloned, borrowed);
+
+    g.remove_mut(ids[1]);
+    assert_eq!(cloned.len(), 3);
+    assert_eq!(g[ids[0]].neighbors_cloned().count(), 0);
+}
+
+#[test]
+fn test_remove_returning() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[2], 33);
+    let before = g.clone();
+    let generation = g.current_generation();
+
+    let removed = g.remove_returning_mut(ids[2]).unwrap();
+    assert_eq!(removed.id, ids[2]);
+    assert_eq!(removed.data, 3);
+    assert_eq!(
+        removed.outbound,
+        vec![(ids[1], 32), (ids[2], 33), (ids[3], 34)]
+    );
+    assert_eq!(removed.inbound, vec![(ids[1], 23), (ids[2], 33)]);
+    assert_ne!(g.current_generation(), generation);
+    assert!(!g.has_vertex(ids[2]));
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(before.edge_count(), 6);
+
+    let generation = g.current_generation();
+    assert_eq!(g.remove_returning_mut(ids[2]), None);
+    assert_eq!(g.remove_returning_mut(b_ids[0]), None);
+    assert_eq!(g.current_generation(), generation);
+}
+
+#[test]
+fn test_all_any_present() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+
+    assert!(g.all_present(&ids));
+    assert!(g.any_present(&ids));
+    assert!(!g.all_present(ids.iter().chain(&b_ids)));
+    assert!(g.any_present(b_ids.iter().chain(&ids)));
+    assert!(!g.any_present(&b_ids));
+
+    g.remove_mut(ids[0]);
+    assert!(!g.all_present(&ids));
+    assert!(g.all_present(&ids[1..]));
+    assert!(!g.any_present(&ids[..1]));
+
+    assert!(g.all_present(Vec::<Id>::new()));
+    assert!(!g.any_present(Vec::<Id>::new()));
+}
+
+#[test]
+fn test_index_by() {
+    let (ids, mut g) = create_vertices();
+
+    let by_data = g.index_by(|&data| data);
+    assert_eq!(by_data.len(), 4);
+    assert_eq!(by_data[&1], ids[0]);
+    assert_eq!(by_data[&4], ids[3]);
+
+    let by_parity = g.index_by(|&data| data % 2);
+    assert_eq!(by_parity.len(), 2);
+    assert_eq!(by_parity[&1], ids[2]);
+    assert_eq!(by_parity[&0], ids[3]);
+
+    g.remove_mut(ids[3]);
+    assert_eq!(g.index_by(|&data| data % 2)[&0], ids[1]);
+    assert!(PGraph::<usize, usize>::new()
+        .index_by(|&data| data)
+        .is_empty());
+}
+
+#[test]
+fn test_is_forest() {
+    let (ids, mut g) = create_vertices();
+    assert!(g.is_forest());
+    assert!(!g.is_tree());
+    assert!(PGraph::<usize, usize>::new().is_forest());
+    assert!(!PGraph::<usize, usize>::new().is_tree());
+
+    add_edges(&ids, &mut g);
+    assert!(!g.is_forest());
+
+    g.disconnect_mut(ids[3], ids[1]);
+    assert!(g.is_forest());
+    assert!(g.is_tree());
+
+    g.connect_mut(ids[3], ids[3], 44);
+    assert!(!g.is_forest());
+    g.disconnect_mut(ids[3], ids[3]);
+
+    g.remove_mut(ids[2]);
+    assert!(g.is_forest());
+    assert!(!g.is_tree());
+
+    let mut u = PGraph::<usize, usize>::with_union_find();
+    let u_ids: Vec<_> = u.add_all_mut(vec![1usize, 2, 3]);
+    u.connect_mut(u_ids[0], u_ids[1], 12);
+    u.connect_mut(u_ids[1], u_ids[2], 23);
+    assert!(u.is_tree());
+    u.connect_mut(u_ids[2], u_ids[0], 31);
+    assert!(!u.is_tree());
+}
+
+#[test]
+fn test_into_parts() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[0]);
+    let shared = g.clone();
+
+    let (vertices, edges) = g.into_parts();
+    assert_eq!(
+        vertices,
+        vec![
+            None,
+            Some((ids[1], 2)),
+            Some((ids[2], 3)),
+            Some((ids[3], 4))
+        ]
+    );
+    assert_eq!(
+        edges,
+        vec![
+            (ids[1], ids[2], 23),
+            (ids[2], ids[1], 32),
+            (ids[2], ids[3], 34),
+            (ids[3], ids[1], 42)
+        ]
+    );
+    assert_eq!(shared[(ids[1],)], 2);
+    assert_eq!(shared.edge_count(), 4);
+
+    let (vertices, edges) = PGraph::<usize, usize>::new().into_parts();
+    assert!(vertices.is_empty());
+    assert!(edges.is_empty());
+
+    let mut g = PGraph::<Vec<u8>, Vec<u8>>::new();
+    let (a, b) = (g.add_mut(vec![1]), g.add_mut(vec![2]));
+    g.connect_mut(a, b, vec![12]);
+    g.connect_mut(b, a, vec![21]);
+    let h = g.clone();
+    g.connect_mut(a, b, vec![120]);
+    g.vertex_data_mut(a).unwrap().push(10);
+    let (data_ptr, weight_ptr) = (g[(a,)].as_ptr(), g[(a, b)].as_ptr());
+
+    let (vertices, edges) = g.into_parts();
+    assert_eq!(vertices[0].as_ref().unwrap().1.as_ptr(), data_ptr);
+    assert_eq!(edges[0], (a, b, vec![120]));
+    assert_eq!(edges[0].2.as_ptr(), weight_ptr);
+    assert_ne!(edges[1].2.as_ptr(), h[(b, a)].as_ptr());
+}
+
+#[test]
+fn test_edges_by_sink() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
+
+    let expected = vec![
+        (ids[0], ids[1], &12),
+        (ids[2], ids[1], &32),
+        (ids[3], ids[1], &42),
+        (ids[1], ids[2], &23),
+        (ids[2], ids[3], &34),
+        (ids[3], ids[3], &44),
+    ];
+    assert_eq!(g.edges_by_sink().collect::<Vec<_>>(), expected);
+
+    let mut indexed = PGraph::<usize, usize>::with_reverse_index();
+    let indexed_ids: Vec<_> = indexed.add_all_mut(1..=4usize);
+    add_edges(&indexed_ids, &mut indexed);
+    indexed.connect_mut(indexed_ids[3], indexed_ids[3], 44);
+    let weights: Vec<_> = indexed
+        .edges_by_sink()
+        .map(|(_, _, &weight)| weight)
+        .collect();
+    assert_eq!(weights, vec![12, 32, 42, 23, 34, 44]);
+}
+
+#[test]
+fn test_shrink_adjacencies() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[1], ids[0], 21);
+    let before = g.clone();
+
+    g.remove_mut(ids[3]);
+    g.disconnect_mut(ids[1], ids[2]);
+    assert_eq!(g[ids[1]].neighbor_slots(), 3);
+    assert_eq!(g[ids[2]].neighbor_slots(), 4);
+
+    g.shrink_adjacencies_mut();
+    assert_eq!(g[ids[0]].neighbor_slots(), 2);
+    assert_eq!(g[ids[1]].neighbor_slots(), 1);
+    assert_eq!(g[ids[2]].neighbor_slots(), 2);
+    assert_eq!(g[(ids[1], ids[0])], 21);
+    assert_eq!(g[(ids[2], ids[1])], 32);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.validate(), Ok(()));
+
+    assert_eq!(before[ids[2]].neighbor_slots(), 4);
+    assert_eq!(before.edge_count(), 6);
+}
+
+#[test]
+fn test_get_vertex_and_edge() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[3]);
+
+    assert_eq!(g.get_vertex(ids[0]).map(Vertex::id), Some(ids[0]));
+    assert!(g.get_vertex(ids[3]).is_none());
+    assert!(g.get_vertex(b_ids[0]).is_none());
+
+    assert_eq!(
+        g.get_edge_indexed(ids[0], ids[1]),
+        Some(&g[(ids[0], ids[1])])
+    );
+    assert_eq!(g.get_edge_indexed(ids[2], ids[1]), Some(&32));
+    assert!(g.get_edge_indexed(ids[2], ids[3]).is_none());
+    assert!(g.get_edge_indexed(ids[3], ids[1]).is_none());
+    assert!(g.get_edge_indexed(b_ids[0], b_ids[1]).is_none());
+}
+
+#[test]
+fn test_connect_path() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    assert!(g.connect_path_mut(vec![ids[3], ids[2], ids[1], ids[0], ids[0]], 0));
+    assert_eq!(g.edge_count(), 8);
+    assert_eq!(g[(ids[2], ids[1])], 0);
+    assert_eq!(g[(ids[0], ids[0])], 0);
+    assert_eq!(g[(ids[0], ids[1])], 12);
+
+    let before = g.clone();
+    assert!(!g.connect_path_mut(vec![ids[0], ids[2], b_ids[0], ids[3]], 1));
+    assert_eq!(
+        g.edges().collect::<Vec<_>>(),
+        before.edges().collect::<Vec<_>>()
+    );
+
+    assert!(g.connect_path_mut(vec![ids[0]], 1));
+    assert!(!g.connect_path_mut(vec![b_ids[0]], 1));
+    assert!(g.connect_path_mut(vec![], 1));
+    assert_eq!(g.edge_count(), 8);
+}
+
+#[test]
+fn test_eq_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of(g: &PGraph<usize, usize>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        g.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let mut h = PGraph::new();
+    let scratch = h.add_mut(0);
+    let h_ids: Vec<_> = h.add_all_mut(vec![1usize, 2, 3, 4]);
+    h.remove_mut(scratch);
+    add_edges(&h_ids, &mut h);
+
+    assert_eq!(g, h);
+    assert_eq!(hash_of(&g), hash_of(&h));
+    assert_ne!(g.count_slots(), h.count_slots());
+
+    let (canon, _) = h.canonicalize();
+    assert_eq!(g, canon);
+    assert_eq!(hash_of(&g), hash_of(&canon));
+
+    h.connect_mut(h_ids[0], h_ids[1], 21);
+    assert_ne!(g, h);
+    assert_ne!(hash_of(&g), hash_of(&h));
+
+    h.connect_mut(h_ids[0], h_ids[1], 12);
+    assert_eq!(g, h);
+    h.disconnect_mut(h_ids[0], h_ids[1]);
+    h.connect_mut(h_ids[0], h_ids[2], 12);
+    assert_ne!(g, h);
+
+    assert_ne!(g, g.remove(ids[3]));
+    assert_eq!(PGraph::<usize, usize>::new(), PGraph::new());
+}
+
+#[test]
+fn test_split_component() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let extra: Vec<_> = g.add_all_mut(vec![5usize, 6]);
+    g.connect_mut(extra[1], extra[0], 65);
+    let gen = g.current_generation();
+
+    let (h, id_map) = g.split_component_mut(ids[3]).unwrap();
+    assert_eq!(id_map.len(), 4);
+    assert_eq!(h.iter_data().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(h.edge_count(), 5);
+    assert_eq!(h[(id_map[&ids[3]], id_map[&ids[1]])], 42);
+
+    assert_ne!(g.current_generation(), gen);
+    assert_eq!(g.ids().collect::<Vec<_>>(), extra);
+    assert_eq!(
+        g.edges().collect::<Vec<_>>(),
+        vec![(extra[1], extra[0], &65)]
+    );
+    assert!(g.split_component_mut(ids[0]).is_none());
+
+    let mut u = PGraph::<usize, usize>::with_union_find();
+    let u_ids: Vec<_> = u.add_all_mut(vec![1usize, 2, 3]);
+    u.connect_mut(u_ids[0], u_ids[1], 12);
+    let (v, _) = u.split_component_mut(u_ids[1]).unwrap();
+    assert_eq!((u.component_count(), v.component_count()), (1, 1));
+}
+
+#[test]
+fn test_or_insert_reserving() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    assert_eq!(g[ids[0]].neighbor_slots(), 2);
+
+    *g.edge(ids[0], ids[1]).or_insert_reserving(0, 9) += 1;
+    assert_eq!(g[(ids[0], ids[1])], 13);
+    assert_eq!(g[ids[0]].neighbor_slots(), 2);
+
+    *g.edge(ids[0], ids[2]).or_insert_reserving(0, usize::MAX) += 1;
+    assert_eq!(g[(ids[0], ids[2])], 1);
+    assert_eq!(g[ids[0]].neighbor_slots(), 4);
+
+    let _: Vec<Id> = g.add_all_mut(5usize..=10);
+    *g.edge(ids[0], ids[3]).or_insert_reserving(0, 7) += 1;
+    assert_eq!(g[ids[0]].neighbor_slots(), 8);
+}
+
+#[test]
+fn test_iterators_unaffected_by_clone() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let expected = g.snapshot_edges();
+
+    let mut h = g.clone();
+    let edges = g.edges();
+    let outbound = g.outbound_edges(ids[2]);
+    h.remove_mut(ids[1]);
+    h.connect_mut(ids[2], ids[0], 31);
+    *h.weight_mut(ids[2], ids[3]).unwrap() = 0;
+
+    let edges: Vec<_> = edges
+        .map(|(source, sink, &weight)| (source, sink, weight))
+        .collect();
+    assert_eq!(edges, expected);
+    let outbound: Vec<_> = outbound.map(|(_, sink, &weight)| (sink, weight)).collect();
+    assert_eq!(outbound, vec![(ids[1], 32), (ids[3], 34)]);
+    assert_eq!(h.edge_count(), 2);
+
+    let snapshot = g.snapshot_edges();
+    g.remove_mut(ids[2]);
+    assert_eq!(snapshot, expected);
+    assert_eq!(g.edge_count(), 2);
+}
+
+#[test]
+fn test_connect_or_create() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[1]);
+
+    let (source, sink) = g.connect_or_create_mut(5, 6, 56);
+    assert!(!ids.contains(&source) && !ids.contains(&sink));
+    assert_eq!((g[(source,)], g[(sink,)]), (5, 6));
+    assert_eq!(
+        g.outbound_edges(source).collect::<Vec<_>>(),
+        vec![(source, sink, &56)]
+    );
+    assert_eq!(g.predecessor_ids(sink).collect::<Vec<_>>(), vec![source]);
+    assert_eq!(g.ids().count(), 5);
+    assert_eq!(g.edge_count(), 2);
+}
+
+#[test]
+fn test_is_source_sink() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let lone = g.add_mut(5);
+
+    assert!(ids.iter().all(|id| g[id].is_source()));
+    assert!(!g[lone].is_source());
+    assert!(ids.iter().all(|id| !g.is_sink(id)));
+    assert!(!g.is_sink(lone));
+    assert!(!g.is_sink(b_ids[1]));
+
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[2], ids[3]);
+    assert!(!g[ids[2]].is_source());
+    assert!(g.is_sink(ids[2]));
+
+    let mut r = g.clone();
+    r.disconnect_mut(ids[1], ids[2]);
+    assert!(!r.is_sink(ids[2]));
+    assert!(g.is_sink(ids[2]));
+}
+
+#[test]
+fn test_connect_no_self_loop() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    let before = g.snapshot_edges();
+
+    for &id in &ids {
+        assert!(!g.connect_no_self_loop_mut(id, id, 0));
+    }
+    assert_eq!(g.snapshot_edges(), before);
+    assert_eq!(g.self_loops().count(), 0);
+
+    assert!(g.connect_no_self_loop_mut(ids[0], ids[1], 0));
+    assert!(g.connect_no_self_loop_mut(ids[1], ids[0], 21));
+    assert_eq!(g[(ids[0], ids[1])], 0);
+    assert_eq!(g[(ids[1], ids[0])], 21);
+    assert_eq!(g.edge_count(), before.len() + 1);
+}
+
+#[test]
+fn test_vertices_mut() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[0]);
+    let h = g.clone();
+
+    let mut visited = vec![];
+    for vertex in g.vertices_mut() {
+        visited.push(vertex.id());
+        *vertex.data_mut() += 10;
+        if let Some(weight) = vertex.weight_mut(ids[1]) {
+            *weight = 0;
+        }
+    }
+
+    assert_eq!(visited, ids[1..].to_vec());
+    assert_eq!(g.iter_data().cloned().collect::<Vec<_>>(), vec![12, 13, 14]);
+    assert_eq!(
+        g.predecessors(ids[1])
+            .map(|(_, _, &w)| w)
+            .collect::<Vec<_>>(),
+        vec![0, 0]
+    );
+    assert_eq!(h.iter_data().cloned().collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(h[(ids[2], ids[1])], 32);
+}
+
+#[test]
+fn test_disconnect_all_to() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[1], ids[1], 22);
+    let h = g.clone();
+
+    assert_eq!(g.disconnect_all_to_mut(ids[1]), 4);
+    assert_eq!(g.in_degree(ids[1]), 0);
+    assert_eq!(g.outbound_ids(ids[1]).collect::<Vec<_>>(), vec![ids[2]]);
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(h.in_degree(ids[1]), 4);
+    assert_eq!(g.disconnect_all_to_mut(b_ids[1]), 0);
+
+    let mut r = PGraph::<usize, usize>::with_reverse_index();
+    let r_ids: Vec<_> = r.add_all_mut(vec![1usize, 2, 3]);
+    r.connect_mut(r_ids[0], r_ids[2], 13);
+    r.connect_mut(r_ids[1], r_ids[2], 23);
+    assert_eq!(r.disconnect_all_to_mut(r_ids[2]), 2);
+    assert_eq!(r.predecessor_ids(r_ids[2]).count(), 0);
+
+    let mut u = PGraph::<usize, usize>::with_union_find();
+    let u_ids: Vec<_> = u.add_all_mut(vec![1usize, 2, 3]);
+    u.connect_mut(u_ids[0], u_ids[1], 12);
+    u.connect_mut(u_ids[1], u_ids[2], 23);
+    assert_eq!(u.disconnect_all_to_mut(u_ids[1]), 1);
+    assert_eq!(u.component_count(), 2);
+}
+
+#[test]
+fn test_outbound_edges_from() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[0], 31);
+
+    let sinks = |g: &PGraph<usize, usize>, source: Id, after: Id| -> Vec<Id> {
+        g.outbound_edges_from(source, after)
+            .map(|(_, sink, _)| sink)
+            .collect()
+    };
+    assert_eq!(sinks(&g, ids[2], ids[0]), vec![ids[1], ids[3]]);
+    assert_eq!(sinks(&g, ids[2], ids[1]), vec![ids[3]]);
+    assert_eq!(sinks(&g, ids[2], ids[2]), vec![ids[3]]);
+    assert!(sinks(&g, ids[2], ids[3]).is_empty());
+    assert!(sinks(&g, ids[0], ids[3]).is_empty());
+    assert!(sinks(&g, b_ids[2], ids[0]).is_empty());
+    assert_eq!(
+        g.outbound_edges_from(ids[2], ids[0]).collect::<Vec<_>>(),
+        vec![(ids[2], ids[1], &32), (ids[2], ids[3], &34)]
+    );
+
+    let extra = g.add_mut(5);
+    assert!(sinks(&g, ids[2], extra).is_empty());
+}
+
+#[test]
+fn test_no_generations() {
+    let mut g = PGraph::<usize, usize>::new_no_generations();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    add_edges(&ids, &mut g);
+    let gen = g.current_generation();
+    assert!(!g.has_generations());
+    assert!(PGraph::<usize, usize>::new().has_generations());
+
+    g.remove_mut(ids[1]);
+    g.remove_all_mut(vec![ids[3]]);
+    assert_eq!(g.current_generation(), gen);
+    let added = g.add_mut(5);
+    assert_eq!(added, ids[1]);
+    assert_eq!(g[(ids[1],)], 5);
+    assert!(!g.has_edge(ids[0], ids[1]));
+
+    let mut h = g.clone();
+    assert_eq!(h.current_generation(), gen);
+    assert!(!h.has_generations());
+    h.clone_from(&g);
+    assert_eq!(h.current_generation(), gen);
+    let h_added = h.add_mut(6);
+    let g_added = g.add_mut(7);
+    assert_eq!(h_added, g_added);
+
+    let (canon, _) = g.canonicalize();
+    assert!(!canon.has_generations());
+    assert_ne!(canon.current_generation(), gen);
+    assert!(!canon.has_vertex(ids[0]));
+
+    let mut scratch = PGraph::<usize, usize>::new();
+    scratch.clone_from(&g);
+    assert!(!scratch.has_generations());
+    let mut normal = PGraph::<usize, usize>::new();
+    normal.add_mut(1);
+    scratch.clone_from(&normal);
+    assert!(scratch.has_generations());
+    assert_ne!(scratch.current_generation(), normal.current_generation());
+}
+
+#[test]
+fn test_contract_edge() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[2], 33);
+    let gen = g.current_generation();
+
+    assert_eq!(
+        g.contract_edge_mut(ids[0], ids[2], |old, new| *old += new),
+        None
+    );
+    assert_eq!(
+        g.contract_edge_mut(ids[2], ids[2], |old, new| *old += new),
+        None
+    );
+    assert_eq!(
+        g.contract_edge_mut(ids[0], b_ids[1], |old, new| *old += new),
+        None
+    );
+    assert_eq!(g.current_generation(), gen);
+
+    let h = g.clone();
+    assert_eq!(
+        g.contract_edge_mut(ids[1], ids[2], |old, new| *old = (*old).max(new)),
+        Some(ids[1])
+    );
+    assert!(!g.has_vertex(ids[2]));
+    assert_ne!(g.current_generation(), gen);
+    assert_eq!(g[(ids[1],)], 2);
+    assert_eq!(
+        g.snapshot_edges(),
+        vec![
+            (ids[0], ids[1], 12),
+            (ids[1], ids[1], 33),
+            (ids[1], ids[3], 34),
+            (ids[3], ids[1], 42)
+        ]
+    );
+    assert_eq!(h.edge_count(), 6);
+
+    let mut u = PGraph::<usize, usize>::with_union_find();
+    let u_ids: Vec<_> = u.add_all_mut(vec![1usize, 2, 3]);
+    u.connect_mut(u_ids[0], u_ids[1], 12);
+    u.connect_mut(u_ids[1], u_ids[2], 23);
+    u.contract_edge_mut(u_ids[0], u_ids[1], |old, new| *old += new);
+    assert_eq!(u.component_count(), 1);
+    assert!(u.same_component(u_ids[0], u_ids[2]));
+}
+
+#[test]
+fn test_dag_checked() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    assert!(g.is_dag_checked());
+    assert!(!PGraph::<usize, usize>::new().is_dag_checked());
+
+    g.connect_mut(ids[0], ids[1], 12);
+    g.connect_mut(ids[1], ids[2], 23);
+    assert!(g.try_connect_mut(ids[0], ids[2], 13));
+    assert!(g.try_connect_mut(ids[0], ids[2], 31));
+    assert!(!g.try_connect_mut(ids[2], ids[0], 31));
+    assert!(!g.try_connect_mut(ids[3], ids[3], 44));
+    assert_eq!(
+        g.connect_checked(ids[2], ids[1], 32),
+        Err(ConnectError::WouldCycle(ids[2], ids[1]))
+    );
+    assert_eq!(g.connect_checked(ids[2], ids[3], 34), Ok(()));
+    assert!(g.try_connect(ids[3], ids[0], 41).is_none());
+    assert_eq!(g.edge_count(), 4);
+
+    let h = g.clone();
+    assert!(h.is_dag_checked());
+    assert!(g.canonicalize().0.is_dag_checked());
+    let mut plain = PGraph::new();
+    plain.clone_from(&g);
+    assert!(plain.is_dag_checked());
+}
+
+#[test]
+fn test_dag_checked_entries() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    g.connect_mut(ids[0], ids[1], 12);
+    g.connect_mut(ids[1], ids[2], 23);
+
+    assert!(g.try_edge(ids[2], ids[0]).is_none());
+    assert!(g.try_edge(ids[1], ids[1]).is_none());
+    assert!(g.weight_or_default_mut(ids[2], ids[0]).is_none());
+    g.edge(ids[2], ids[0]).and_modify(|weight| *weight += 1);
+    assert_eq!(g.edge_count(), 2);
+
+    *g.edge(ids[0], ids[1]).or_insert(0) += 100;
+    *g.weight_or_default_mut(ids[0], ids[2]).unwrap() += 13;
+    assert_eq!(g[(ids[0], ids[1])], 112);
+    assert_eq!(g[(ids[0], ids[2])], 13);
+    g.connect_mut(ids[1], ids[2], 32);
+    assert_eq!(g.edge_count(), 3);
+}
+
+#[test]
+fn test_dag_checked_derived_graphs() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    g.connect_mut(ids[0], ids[1], 12);
+
+    let c = g.complement();
+    assert!(!c.is_dag_checked());
+    assert_eq!(c.edge_count(), 5);
+    assert_eq!(g.complement_with_self_loops().edge_count(), 8);
+
+    g.connect_mut(ids[1], ids[2], 23);
+    let (h, id_map) = g.canonicalize();
+    assert!(h.is_dag_checked());
+    assert_eq!(h[(id_map[&ids[1]], id_map[&ids[2]])], 23);
+    assert_eq!(g.recreate().edge_count(), 2);
+
+    let mut split = g.clone();
+    let (part, part_ids) = split.split_component_mut(ids[0]).unwrap();
+    assert!(part.is_dag_checked());
+    assert!(part.has_edge(part_ids[&ids[0]], part_ids[&ids[1]]));
+}
+
+#[test]
+fn test_dag_checked_contract_edge() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    g.connect_mut(ids[0], ids[1], 12);
+    g.connect_mut(ids[0], ids[2], 13);
+    g.connect_mut(ids[2], ids[1], 32);
+    g.connect_mut(ids[1], ids[3], 24);
+    let gen = g.current_generation();
+
+    assert_eq!(
+        g.contract_edge_mut(ids[0], ids[1], |old, new| *old += new),
+        None
+    );
+    assert!(g.has_vertex(ids[1]));
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g.current_generation(), gen);
+
+    assert_eq!(
+        g.contract_edge_mut(ids[0], ids[2], |old, new| *old += new),
+        Some(ids[0])
+    );
+    assert_eq!(g[(ids[0], ids[1])], 12 + 32);
+    assert_eq!(
+        g.contract_edge_mut(ids[0], ids[1], |old, new| *old += new),
+        Some(ids[0])
+    );
+    assert_eq!(g.snapshot_edges(), vec![(ids[0], ids[3], 24)]);
+}
+
+#[test]
+fn test_dag_checked_connect_path() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    g.connect_mut(ids[2], ids[0], 31);
+
+    assert!(!g.connect_path_mut(vec![ids[0], ids[1], ids[2]], 1));
+    assert_eq!(g.edge_count(), 1);
+    assert!(!g.connect_path_mut(vec![ids[1], ids[3], ids[1]], 1));
+    assert!(!g.connect_path_mut(vec![ids[3], ids[3]], 1));
+    assert_eq!(g.edge_count(), 1);
+
+    assert!(g.connect_path_mut(vec![ids[1], ids[2], ids[3]], 1));
+    assert!(g.connect_path_mut(vec![ids[1], ids[2]], 2));
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g[(ids[1], ids[2])], 2);
+    assert!(g.transitive_reduction().is_ok());
+}
+
+#[test]
+fn test_dag_checked_bool_connects() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3]);
+    g.connect_mut(ids[0], ids[1], 12);
+    g.connect_mut(ids[1], ids[2], 23);
+
+    assert!(!g.insert_edge_mut(ids[2], ids[0], 31));
+    assert!(!g.connect_if_absent_mut(ids[2], ids[0], 31));
+    assert!(!g.connect_no_self_loop_mut(ids[2], ids[0], 31));
+    assert!(!g.insert_edge_mut(ids[1], ids[1], 22));
+    assert!(!g.connect_if_absent_mut(ids[1], ids[1], 22));
+    assert_eq!(g.edge_count(), 2);
+
+    assert!(!g.insert_edge_mut(ids[0], ids[1], 120));
+    assert_eq!(g[(ids[0], ids[1])], 120);
+    assert!(g.connect_if_absent_mut(ids[0], ids[2], 13));
+    assert!(g.connect_no_self_loop_mut(ids[0], ids[2], 130));
+    assert_eq!(g[(ids[0], ids[2])], 130);
+    assert!(g.transitive_reduction().is_ok());
+}
+
+#[test]
+fn test_edges_compact() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[2], ids[0], 31);
+    g.disconnect_mut(ids[2], ids[1]);
+
+    for id in &ids {
+        let lazy: Vec<_> = g[id].into_iter().collect();
+        assert_eq!(g[id].edges_compact(), lazy);
+    }
+    assert_eq!(
+        g[ids[2]].edges_compact(),
+        vec![(ids[0], &31), (ids[3], &34)]
+    );
+}
+
+#[test]
+fn test_replace_vertex_data() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let h = g.clone();
+
+    assert_eq!(g.replace_vertex_data(ids[2], 30), Some(3));
+    assert_eq!(g.replace_vertex_data(ids[2], 300), Some(30));
+    assert_eq!(g.replace_vertex_data(b_ids[0], 10), None);
+    assert_eq!(g[(ids[2],)], 300);
+    assert_eq!(h[(ids[2],)], 3);
+    assert_eq!(g.edge_count(), h.edge_count());
+}
+
+#[test]
+fn test_edge_count_from_to() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let mut r = PGraph::<usize, usize>::with_reverse_index();
+    let r_ids: Vec<_> = r.add_all_mut(vec![1usize, 2, 3, 4]);
+    add_edges(&r_ids, &mut r);
+
+    let from: Vec<_> = ids.iter().map(|id| g.edge_count_from(id)).collect();
+    let to: Vec<_> = ids.iter().map(|id| g.edge_count_to(id)).collect();
+    assert_eq!(from, vec![1, 1, 2, 1]);
+    assert_eq!(to, vec![0, 3, 1, 1]);
+    assert_eq!(from.iter().sum::<usize>(), g.edge_count());
+    assert_eq!(
+        r_ids
+            .iter()
+            .map(|id| r.edge_count_to(id))
+            .collect::<Vec<_>>(),
+        to
+    );
+
+    assert_eq!(g.edge_count_from(b_ids[0]), 0);
+    assert_eq!(g.edge_count_to(b_ids[1]), 0);
+
+    r.remove_mut(r_ids[2]);
+    assert_eq!(r.edge_count_to(r_ids[1]), 2);
+    assert_eq!(r.edge_count_to(r_ids[2]), 0);
+}
+
+#[test]
+fn test_from_iter() {
+    let g: PGraph<usize, usize> = (1..=4).collect();
+    let (_, expected) = create_vertices();
+
+    assert_eq!(g.ids().count(), 4);
+    assert_eq!(g.edge_count(), 0);
+    for (id, expected_id) in g.ids().zip(expected.ids()) {
+        assert_eq!(g[(id,)], expected[(expected_id,)]);
+    }
+
+    let empty: PGraph<usize, usize> = Vec::new().into_iter().collect();
+    assert_eq!(empty.ids().count(), 0);
+}
+
+#[test]
+fn test_diff() {
+    let (ids, mut old) = create_vertices();
+    add_edges(&ids, &mut old);
+
+    let mut new = old.remove(ids[3]);
+    let added = new.add_mut(5);
+    new.connect_mut(added, ids[0], 51);
+    new[(ids[1], ids[2])] = 0;
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.added_vertices, vec![added]);
+    assert_eq!(diff.removed_vertices, vec![ids[3]]);
+    assert_eq!(diff.added_edges, vec![(added, ids[0])]);
+    assert_eq!(diff.removed_edges, vec![(ids[2], ids[3]), (ids[3], ids[1])]);
+    assert_eq!(diff.changed_edges, vec![(ids[1], ids[2])]);
+
+    let reverse = new.diff(&old);
+    assert_eq!(reverse.added_vertices, diff.removed_vertices);
+    assert_eq!(reverse.removed_edges, diff.added_edges);
+
+    assert!(old.diff(&old.clone()).is_empty());
+
+    assert_eq!(old.edge_difference(&new), diff.removed_edges);
+    assert_eq!(new.edge_difference(&old), diff.added_edges);
+    assert!(old.edge_difference(&old.clone()).is_empty());
+    let (_, unrelated) = create_vertices();
+    assert_eq!(old.edge_difference(&unrelated).len(), old.edge_count());
+}
+
+#[test]
+fn test_clone_from() {
+    let (ids, mut source) = create_vertices();
+    add_edges(&ids, &mut source);
+    let (_, mut scratch) = create_vertices();
+
+    for i in 0..3 {
+        scratch.clone_from(&source);
+        assert_ne!(scratch.generation(), source.generation());
+        assert!(scratch.diff(&source).is_empty());
+
+        let added = scratch.add_mut(5 + i);
+        scratch.connect_mut(added, ids[0], 51);
+        scratch.remove_mut(ids[1]);
+
+        assert!(!source.has_vertex(added));
+        assert!(source.has_vertex(ids[1]));
+        assert_eq!(source.edge_count(), 5);
+    }
+}
+
+#[test]
+fn test_debug() {
+    let (a_ids, mut a) = create_vertices();
+    add_edges(&a_ids, &mut a);
+    let before_gen = a.generation();
+    a.remove_mut(a_ids[0]);
+    a.disconnect_mut(a_ids[3], a_ids[1]);
+    let after_gen = a.generation();
+
+    let result = format!("{:?}", a);
+    let expected = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n",
+        format!("PGraph (gen {}) {{", after_gen),
+        format!("\tBlank"),
+        format!("\t2 (1 gen{}) => (2 gen{}: 23)", before_gen, before_gen),
+        format!(
+            "\t3 (2 gen{}) => (1 gen{}: 32, 3 gen{}: 34)",
+            before_gen, before_gen, before_gen
+        ),
+        format!("\t4 (3 gen{}) => (None)", before_gen),
+        "}"
+    );
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_debug_alternate() {
+    let (a_ids, mut a) = create_vertices();
+    add_edges(&a_ids, &mut a);
+    a.remove_mut(a_ids[0]);
+    a.disconnect_mut(a_ids[3], a_ids[1]);
+
+    let expected = [
+        "PGraph {",
+        "    0: 2",
+        "        -> 1: 23",
+        "    1: 3",
+        "        -> 0: 32",
+        "        -> 2: 34",
+        "    2: 4",
+        "}",
+    ]
+    .join("\n");
+    assert_eq!(format!("{:#?}", a), expected);
+    assert_eq!(format!("{:#?}", a.canonicalize().0), expected);
+    assert_eq!(
+        format!("{:#?}", PGraph::<usize, usize>::new()),
+        "PGraph {\n}"
+    );
 }
 
 #[test]
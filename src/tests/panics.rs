@@ -95,3 +95,50 @@ fn test_connect_mut2() {
 
     a.connect_mut(b_ids[1], a_ids[2], 4);
 }
+
+#[test]
+#[should_panic]
+fn test_connect_add_missing_sink() {
+    let (ids, mut g) = create_vertices();
+    g.remove_mut(ids[1]);
+    g.connect_add_mut(ids[0], ids[1], 1);
+}
+
+#[test]
+#[should_panic(expected = "There is no edge")]
+fn test_get_edge() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    assert!(g.get_edge_indexed(ids[1], ids[0]).is_none());
+    let _weight = g[(ids[1], ids[0])];
+}
+
+#[test]
+#[should_panic(expected = "removed from the graph")]
+fn test_get_edge_removed_source() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.remove_mut(ids[0]);
+
+    assert!(g.get_edge_indexed(ids[0], ids[1]).is_none());
+    let _weight = g[(ids[0], ids[1])];
+}
+
+#[test]
+#[should_panic(expected = "would introduce a cycle")]
+fn test_connect_dag_checked_cycle() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2]);
+    g.connect_mut(ids[0], ids[1], 12);
+    g.connect_mut(ids[1], ids[0], 21);
+}
+
+#[test]
+#[should_panic(expected = "would introduce a cycle")]
+fn test_edge_dag_checked_cycle() {
+    let mut g = PGraph::<usize, usize>::dag_checked();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2]);
+    g.connect_mut(ids[0], ids[1], 12);
+    g.edge(ids[1], ids[0]).or_insert(21);
+}
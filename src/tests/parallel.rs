@@ -0,0 +1,31 @@
+use super::*;
+use rayon::prelude::*;
+
+#[test]
+fn test_par_edges() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+    g.connect_mut(ids[3], ids[3], 44);
+    g.remove_mut(ids[0]);
+
+    let mut expected: Vec<_> = g.edges().collect();
+    let mut actual: Vec<_> = g.par_edges().collect();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+    assert_eq!(g.par_edges().count(), g.edge_count());
+
+    let mut big = PGraph::<usize, usize>::new();
+    let big_ids: Vec<_> = big.add_all_mut(0..200usize);
+    for &source in &big_ids {
+        for &sink in big_ids.iter().step_by(source.index() + 1) {
+            big.connect_mut(source, sink, source.index() + sink.index());
+        }
+    }
+    big.remove_all_mut(big_ids.iter().step_by(7));
+
+    let expected: usize = big.edges().map(|(_, _, weight)| weight).sum();
+    let actual: usize = big.par_edges().map(|(_, _, weight)| weight).sum();
+    assert_eq!(actual, expected);
+    assert_eq!(big.par_edges().count(), big.edge_count());
+}
@@ -0,0 +1,51 @@
+use super::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_sample_neighbor() {
+    let mut g = PGraph::<usize, u32>::new();
+    let ids: Vec<_> = g.add_all_mut(vec![1usize, 2, 3, 4]);
+    let (b_ids, _) = create_vertices();
+    g.connect_mut(ids[0], ids[1], 1);
+    g.connect_mut(ids[0], ids[2], 3);
+    g.connect_mut(ids[0], ids[3], 0);
+    g.connect_mut(ids[1], ids[3], 0);
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut counts = [0; 4];
+    for _ in 0..4000 {
+        let sink = g.sample_neighbor(ids[0], &mut rng).unwrap();
+        counts[ids.iter().position(|&id| id == sink).unwrap()] += 1;
+    }
+    assert_eq!(counts[0], 0);
+    assert_eq!(counts[3], 0);
+    assert!(counts[1] > 800 && counts[1] < 1200);
+    assert_eq!(counts[1] + counts[2], 4000);
+
+    assert_eq!(g.sample_neighbor(ids[1], &mut rng), None);
+    assert_eq!(g.sample_neighbor(ids[3], &mut rng), None);
+    assert_eq!(g.sample_neighbor(b_ids[0], &mut rng), None);
+}
+
+#[test]
+fn test_random_walk() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+    let mut g = g.map(|_, &data| data, |_, _, &weight| weight as u32);
+
+    let mut rng = StdRng::seed_from_u64(2);
+    let walk = g.random_walk(ids[0], 50, &mut rng);
+    assert_eq!(walk.len(), 51);
+    assert_eq!(walk[0], ids[0]);
+    assert!(walk.windows(2).all(|step| g.has_edge(step[0], step[1])));
+
+    g.disconnect_mut(ids[2], ids[1]);
+    g.disconnect_mut(ids[2], ids[3]);
+    let walk = g.random_walk(ids[0], 50, &mut rng);
+    assert_eq!(walk, vec![ids[0], ids[1], ids[2]]);
+
+    assert_eq!(g.random_walk(ids[3], 0, &mut rng), vec![ids[3]]);
+    assert!(g.random_walk(b_ids[0], 5, &mut rng).is_empty());
+}
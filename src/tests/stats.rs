@@ -0,0 +1,210 @@
+use super::*;
+
+#[test]
+fn test_degree_sequence() {
+    let (ids, mut g) = create_vertices();
+    assert_eq!(g.degree_sequence(), vec![0, 0, 0, 0]);
+
+    add_edges(&ids, &mut g);
+    assert_eq!(g.degree_sequence(), vec![4, 3, 2, 1]);
+
+    g.remove_mut(ids[1]);
+    assert_eq!(g.degree_sequence(), vec![1, 1, 0]);
+
+    let degree_sum: usize = g.degree_sequence().iter().sum();
+    assert_eq!(degree_sum, 2 * g.edge_count());
+}
+
+#[test]
+fn test_density() {
+    let (ids, mut g) = create_vertices();
+    assert_eq!(g.density(), 0.0);
+
+    add_edges(&ids, &mut g);
+    assert_eq!(g.density(), 5.0 / 12.0);
+
+    g.remove_all_mut(&ids[1..]);
+    assert_eq!(g.density(), 0.0);
+    assert!(!PGraph::<usize, usize>::new().density().is_nan());
+}
+
+#[test]
+fn test_fragmentation() {
+    let (ids, mut g) = create_vertices();
+    assert_eq!(g.empty_slots(), 0);
+    assert_eq!(g.load_factor(), 1.0);
+
+    g.remove_mut(ids[1]);
+    assert_eq!(g.empty_slots(), 1);
+    assert_eq!(g.load_factor(), 0.75);
+
+    g.remove_mut(ids[3]);
+    assert_eq!(g.empty_slots(), 2);
+    assert_eq!(g.load_factor(), 0.5);
+
+    g.shrink_to_fit_mut();
+    assert_eq!(g.empty_slots(), 1);
+    assert_eq!(g.load_factor(), 2.0 / 3.0);
+
+    g.add_mut(5);
+    assert_eq!(g.empty_slots(), 0);
+    assert_eq!(g.load_factor(), 1.0);
+
+    g.remove_all_mut(g.ids().collect::<Vec<_>>());
+    assert_eq!(g.load_factor(), 0.0);
+    assert!(!PGraph::<usize, usize>::new().load_factor().is_nan());
+}
+
+#[test]
+fn test_top_k_vertices() {
+    let (ids, mut g) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let by_data = g.top_k_vertices(2, |_, data| *data);
+    assert_eq!(by_data, vec![(ids[3], 4), (ids[2], 3)]);
+    assert!(g.top_k_vertices(0, |_, data| *data).is_empty());
+    assert_eq!(g.top_k_vertices(5, |_, data| *data).len(), 4);
+
+    let by_in_degree = g.top_k_vertices(2, |id, _| g.in_degree(id));
+    assert_eq!(by_in_degree[0], (ids[1], 3));
+    assert_eq!(by_in_degree[1].1, 1);
+    assert!(by_in_degree[1].0 == ids[2] || by_in_degree[1].0 == ids[3]);
+
+    g.remove_mut(ids[3]);
+    let by_data = g.top_k_vertices(2, |_, data| *data);
+    assert_eq!(by_data, vec![(ids[2], 3), (ids[1], 2)]);
+}
+
+#[test]
+fn test_weight_histogram() {
+    let (ids, mut g) = create_vertices();
+    assert!(g.weight_histogram(|w| *w).is_empty());
+
+    add_edges(&ids, &mut g);
+    let by_tens = g.weight_histogram(|w| w / 10);
+    assert_eq!(by_tens.len(), 4);
+    assert_eq!(by_tens[&1], 1);
+    assert_eq!(by_tens[&2], 1);
+    assert_eq!(by_tens[&3], 2);
+    assert_eq!(by_tens[&4], 1);
+
+    let by_parity = g.weight_histogram(|w| w % 2 == 0);
+    assert_eq!(by_parity[&true], 4);
+    assert_eq!(by_parity[&false], 1);
+    assert_eq!(by_parity.values().sum::<usize>(), g.edge_count());
+}
+
+#[test]
+fn test_total_weight() {
+    let (ids, mut g) = create_vertices();
+    assert_eq!(g.total_weight(), 0);
+
+    add_edges(&ids, &mut g);
+    assert_eq!(g.total_weight(), 12 + 23 + 32 + 34 + 42);
+
+    let out_total: usize = ids.iter().map(|id| g.weighted_out_degree(id)).sum();
+    assert_eq!(g.total_weight(), out_total);
+
+    g.remove_mut(ids[1]);
+    assert_eq!(g.total_weight(), 34);
+}
+
+#[test]
+fn test_weighted_degree() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    add_edges(&ids, &mut g);
+
+    let out: Vec<usize> = ids.iter().map(|id| g.weighted_out_degree(id)).collect();
+    let inc: Vec<usize> = ids.iter().map(|id| g.weighted_in_degree(id)).collect();
+    assert_eq!(out, vec![12, 23, 66, 42]);
+    assert_eq!(inc, vec![0, 86, 23, 34]);
+    assert_eq!(out.iter().sum::<usize>(), inc.iter().sum::<usize>());
+
+    g.connect_mut(ids[0], ids[0], 100);
+    assert_eq!(g.weighted_out_degree(ids[0]), 112);
+    assert_eq!(g.weighted_in_degree(ids[0]), 100);
+
+    assert_eq!(g.weighted_out_degree(b_ids[0]), 0);
+    assert_eq!(g.weighted_in_degree(b_ids[1]), 0);
+}
+
+#[test]
+fn test_symmetry() {
+    let (ids, mut g) = create_vertices();
+    assert!(g.is_symmetric());
+
+    add_edges(&ids, &mut g);
+    assert!(!g.is_symmetric());
+    let asymmetric: Vec<_> = g.asymmetric_edges().collect();
+    assert_eq!(
+        asymmetric,
+        vec![
+            (ids[0], ids[1]),
+            (ids[1], ids[2]),
+            (ids[2], ids[1]),
+            (ids[2], ids[3]),
+            (ids[3], ids[1])
+        ]
+    );
+
+    g.connect_mut(ids[1], ids[0], 12);
+    g.connect_mut(ids[2], ids[1], 23);
+    g.connect_mut(ids[3], ids[2], 34);
+    g.connect_mut(ids[1], ids[3], 42);
+    g.connect_mut(ids[3], ids[3], 44);
+    assert!(g.is_symmetric());
+
+    g.connect_mut(ids[3], ids[2], 43);
+    assert_eq!(
+        g.asymmetric_edges().collect::<Vec<_>>(),
+        vec![(ids[2], ids[3]), (ids[3], ids[2])]
+    );
+}
+
+#[test]
+fn test_structural_stats() {
+    let (ids, mut g) = create_vertices();
+    let empty = g.structural_stats();
+    assert_eq!(empty.vertices, 4);
+    assert_eq!(empty.empty_slots, 0);
+    assert_eq!(empty.adjacency_slots, 0);
+
+    add_edges(&ids, &mut g);
+    let connected = g.structural_stats();
+    assert_eq!(connected.adjacency_slots, 2 + 3 + 4 + 2);
+    assert!(connected.approx_bytes > empty.approx_bytes);
+
+    g.remove_mut(ids[3]);
+    let removed = g.structural_stats();
+    assert_eq!(removed.vertices, 3);
+    assert_eq!(removed.empty_slots, 1);
+    assert!(removed.approx_bytes < connected.approx_bytes);
+
+    g.shrink_to_fit_mut();
+    assert_eq!(g.structural_stats().empty_slots, 0);
+}
+
+#[test]
+fn test_min_max_edge() {
+    let (ids, mut g) = create_vertices();
+    let (b_ids, _) = create_vertices();
+    assert_eq!(g.min_edge(), None);
+    assert_eq!(g.max_edge(), None);
+
+    add_edges(&ids, &mut g);
+    assert_eq!(g.min_edge(), Some((ids[0], ids[1], &12)));
+    assert_eq!(g.max_edge(), Some((ids[3], ids[1], &42)));
+    assert_eq!(g.min_out_edge(ids[2]), Some((ids[2], ids[1], &32)));
+    assert_eq!(g.max_out_edge(ids[2]), Some((ids[2], ids[3], &34)));
+    assert_eq!(g.min_out_edge(b_ids[2]), None);
+    assert_eq!(g.max_out_edge(b_ids[2]), None);
+
+    g.connect_mut(ids[1], ids[0], 12);
+    g.connect_mut(ids[2], ids[0], 42);
+    g.connect_mut(ids[2], ids[2], 34);
+    assert_eq!(g.min_edge(), Some((ids[0], ids[1], &12)));
+    assert_eq!(g.max_edge(), Some((ids[2], ids[0], &42)));
+    assert_eq!(g.min_out_edge(ids[1]), Some((ids[1], ids[0], &12)));
+    assert_eq!(g.max_out_edge(ids[2]), Some((ids[2], ids[0], &42)));
+}